@@ -2,15 +2,20 @@ use criterion::{black_box, Criterion, criterion_group, criterion_main};
 use rand::prelude::SliceRandom;
 
 use mokareads_core::resources::article::{Article, Metadata};
+use mokareads_core::resources::Cacher;
 
-fn sync_find(data: &[Article], target: &Article) -> Article {
+fn sync_find<'a>(data: &'a [Article], target: &Article) -> Option<&'a Article> {
     Article::find(data, &target.slug)
 }
 
-fn binary_search(data: &[Article], target: &Article) -> Article {
+fn binary_search<'a>(data: &'a [Article], target: &Article) -> Option<&'a Article> {
     Article::search(data, &target.slug)
 }
 
+fn hash_lookup<'a>(cacher: &'a Cacher, target: &Article) -> Option<&'a Article> {
+    cacher.article(&target.slug)
+}
+
 fn special_article() -> Article {
     Article::new(
         Metadata::new("special", "special", "special", "special", "special"),
@@ -23,7 +28,7 @@ fn fill_by_size(size: usize) -> Vec<Article> {
     let mut vec = Vec::with_capacity(size);
     vec.push(special);
 
-    for i in 1..size {
+    for _ in 1..size {
         vec.push(Article::default())
     }
 
@@ -42,22 +47,36 @@ fn benchmark(c: &mut Criterion) {
         data.shuffle(&mut rng);
 
         let special = special_article();
+        let cacher = Cacher::new(data.clone(), Vec::new(), Vec::new());
 
         c.bench_function(
             &format!("Sync Find - Size {size}"),
             |b| {
                 b.iter(|| {
-                    let result = sync_find(&fill_by_size(size), &special);
+                    let result = sync_find(&data, &special);
                     black_box(result)
                 })
             },
         );
 
+        // binary search requires the articles to be sorted by slug
+        let mut sorted = data.clone();
+        sorted.sort_by(|a, b| a.slug.cmp(&b.slug));
         c.bench_function(
             &format!("Binary Search - Size {size}"),
             |b| {
                 b.iter(|| {
-                    let result = binary_search(&fill_by_size(size), &special);
+                    let result = binary_search(&sorted, &special);
+                    black_box(result)
+                })
+            },
+        );
+
+        c.bench_function(
+            &format!("Hash Lookup - Size {size}"),
+            |b| {
+                b.iter(|| {
+                    let result = hash_lookup(&cacher, &special);
                     black_box(result)
                 })
             },
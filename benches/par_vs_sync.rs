@@ -19,11 +19,6 @@ fn parallel_linear_search(data: &[Cheatsheet], target: &Cheatsheet) -> Option<Ch
         .cloned()
 }
 
-// Async linear search
-async fn async_linear_search(data: &[Cheatsheet], target: &Cheatsheet) -> Cheatsheet {
-    Cheatsheet::fetch(data, target.slug.clone(), target.lang()).await
-}
-
 fn fill_by_size(size: usize) -> Vec<Cheatsheet> {
     let one_to_find = Cheatsheet::new(
         Metadata::new("special", "special", 1, "special", "special"),
@@ -32,7 +27,7 @@ fn fill_by_size(size: usize) -> Vec<Cheatsheet> {
     let mut vec = Vec::with_capacity(size);
     vec.push(one_to_find);
 
-    for i in 1..size {
+    for _ in 1..size {
         vec.push(Cheatsheet::default())
     }
 
@@ -0,0 +1,37 @@
+/// This benchmark is for per-keystroke suggestions
+use criterion::{black_box, Criterion, criterion_group, criterion_main};
+use lipsum::lipsum_title;
+
+use mokareads_core::resources::article::{Article, Metadata};
+use mokareads_core::resources::{Cacher, Searcher};
+
+fn fill_by_size(size: usize) -> Vec<Article> {
+    (0..size)
+        .map(|_| {
+            Article::new(
+                Metadata::new(&lipsum_title(), "description", "author", "devicon", "rust, async"),
+                "content".to_string(),
+            )
+        })
+        .collect()
+}
+
+fn benchmark(c: &mut Criterion) {
+    let dataset_sizes = vec![1_000, 5_000];
+
+    for size in dataset_sizes {
+        let cacher = Cacher::new(fill_by_size(size), Vec::new(), Vec::new());
+        let searcher = Searcher::new(&cacher);
+
+        c.bench_function(&format!("Suggest Prefix - Size {size}"), |b| {
+            b.iter(|| black_box(searcher.suggest("lor", 10)))
+        });
+
+        c.bench_function(&format!("Suggest Typo - Size {size}"), |b| {
+            b.iter(|| black_box(searcher.suggest("dolor consectetru", 10)))
+        });
+    }
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
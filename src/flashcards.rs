@@ -0,0 +1,351 @@
+use std::collections::BTreeMap;
+
+use pulldown_cmark::{Event, Options, Parser, Tag};
+
+use crate::resources::cheatsheet::{Cheatsheet, Language, Level};
+
+/// A flashcard made from a cheat sheet entry, where the front is the entry's description
+/// (or its section heading when there is none) and the back is the code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flashcard {
+    pub front: String,
+    pub back: String,
+    /// The slug of the cheat sheet the card was made from
+    pub source: String,
+}
+
+impl Flashcard {
+    /// The front of the card as html
+    pub fn front_html(&self) -> String {
+        escape_html(&self.front)
+    }
+    /// The back of the card as an html code block
+    pub fn back_html(&self) -> String {
+        format!("<pre><code>{}</code></pre>", escape_html(&self.back))
+    }
+}
+
+/// A deck of flashcards for a language and level
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deck {
+    pub lang: Language,
+    pub level: Level,
+    pub cards: Vec<Flashcard>,
+}
+
+impl Deck {
+    /// The name of the deck, using `::` to nest it under its language, e.g. `MoKa Reads::Rust::Beginner`
+    pub fn name(&self) -> String {
+        format!("MoKa Reads::{}::{:?}", self.lang.display_name(), self.level)
+    }
+    /// The tags given to every card in the deck
+    fn tags(&self) -> String {
+        format!("mokareads {} {:?}", self.lang, self.level).to_lowercase()
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Turns the entries of a raw cheat sheet into flashcards.
+///
+/// Every code block becomes a card, with the paragraph or list item just before it as the front,
+/// falling back to the heading of its section. Code blocks with neither are skipped.
+pub fn from_cheatsheet(cheatsheet: &Cheatsheet) -> Vec<Flashcard> {
+    let mut cards = Vec::new();
+    let mut heading = String::new();
+    let mut description: Option<String> = None;
+    let mut text = String::new();
+    let mut code: Option<String> = None;
+
+    for event in Parser::new_ext(cheatsheet.content(), Options::all()) {
+        match event {
+            Event::Start(Tag::Heading(..)) | Event::Start(Tag::CodeBlock(_)) => {
+                text.clear();
+                if matches!(event, Event::Start(Tag::CodeBlock(_))) {
+                    code = Some(String::new());
+                }
+            }
+            Event::End(Tag::Heading(..)) => {
+                heading = text.trim().to_string();
+                description = None;
+                text.clear();
+            }
+            Event::End(Tag::Paragraph) | Event::End(Tag::Item) => {
+                if !text.trim().is_empty() {
+                    description = Some(text.trim().to_string());
+                }
+                text.clear();
+            }
+            Event::End(Tag::CodeBlock(_)) => {
+                let back = code.take().unwrap_or_default();
+                let front = description.take().unwrap_or_else(|| heading.clone());
+                if !front.is_empty() && !back.trim().is_empty() {
+                    cards.push(Flashcard {
+                        front,
+                        back: back.trim_end().to_string(),
+                        source: cheatsheet.slug.clone(),
+                    });
+                }
+            }
+            Event::Text(t) => match code.as_mut() {
+                Some(code) => code.push_str(&t),
+                None => text.push_str(&t),
+            },
+            Event::Code(t) => text.push_str(&format!("`{t}`")),
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            _ => {}
+        }
+    }
+    cards
+}
+
+/// Groups the flashcards of the cheat sheets into decks by language and level
+pub fn decks(cheatsheets: &[Cheatsheet]) -> Vec<Deck> {
+    let mut grouped: BTreeMap<(Language, Level), Vec<Flashcard>> = BTreeMap::new();
+    for cheatsheet in cheatsheets {
+        let cards = from_cheatsheet(cheatsheet);
+        if !cards.is_empty() {
+            grouped
+                .entry((cheatsheet.language(), cheatsheet.level()))
+                .or_default()
+                .extend(cards);
+        }
+    }
+    grouped
+        .into_iter()
+        .map(|((lang, level), cards)| Deck { lang, level, cards })
+        .collect()
+}
+
+/// Writes the decks as tab separated values that Anki can import directly,
+/// with the html front and back followed by the deck name and tags of each card
+pub fn to_tsv(decks: &[Deck]) -> String {
+    let mut tsv = String::from("#separator:tab\n#html:true\n#deck column:3\n#tags column:4\n");
+    for deck in decks {
+        let (name, tags) = (deck.name(), deck.tags());
+        for card in &deck.cards {
+            let fields = [card.front_html(), card.back_html()]
+                .map(|x| x.replace('\t', "    ").replace('\n', "<br>"));
+            tsv.push_str(&format!(
+                "{}\t{}\t{}\t{} {}\n",
+                fields[0], fields[1], name, tags, card.source
+            ));
+        }
+    }
+    tsv
+}
+
+#[cfg(feature = "anki")]
+pub use apkg::write_apkg;
+
+#[cfg(feature = "anki")]
+mod apkg {
+    use std::io::Write;
+    use std::path::Path;
+
+    use serde_json::json;
+
+    use super::Deck;
+
+    /// The id of the note type used for every card
+    const MODEL_ID: i64 = 1_697_040_000_000;
+
+    const SCHEMA: &str = "
+CREATE TABLE col (id integer primary key, crt integer not null, mod integer not null,
+    scm integer not null, ver integer not null, dty integer not null, usn integer not null,
+    ls integer not null, conf text not null, models text not null, decks text not null,
+    dconf text not null, tags text not null);
+CREATE TABLE notes (id integer primary key, guid text not null, mid integer not null,
+    mod integer not null, usn integer not null, tags text not null, flds text not null,
+    sfld integer not null, csum integer not null, flags integer not null, data text not null);
+CREATE TABLE cards (id integer primary key, nid integer not null, did integer not null,
+    ord integer not null, mod integer not null, usn integer not null, type integer not null,
+    queue integer not null, due integer not null, ivl integer not null, factor integer not null,
+    reps integer not null, lapses integer not null, left integer not null, odue integer not null,
+    odid integer not null, flags integer not null, data text not null);
+CREATE TABLE revlog (id integer primary key, cid integer not null, usn integer not null,
+    ease integer not null, ivl integer not null, lastIvl integer not null, factor integer not null,
+    time integer not null, type integer not null);
+CREATE TABLE graves (usn integer not null, oid integer not null, type integer not null);
+CREATE INDEX ix_notes_usn on notes (usn);
+CREATE INDEX ix_cards_usn on cards (usn);
+CREATE INDEX ix_revlog_usn on revlog (usn);
+CREATE INDEX ix_cards_nid on cards (nid);
+CREATE INDEX ix_cards_sched on cards (did, queue, due);
+CREATE INDEX ix_revlog_cid on revlog (cid);
+CREATE INDEX ix_notes_csum on notes (csum);
+";
+
+    /// A stable 64 bit FNV-1a hash, used for ids that don't change between exports
+    fn fnv(text: &str) -> u64 {
+        text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+    }
+
+    /// Deck ids must be positive and `1` is reserved for the default deck
+    fn deck_id(deck: &Deck) -> i64 {
+        (fnv(&deck.name()) >> 12) as i64 + 2
+    }
+
+    /// The sort field of a note as Anki stores it, the html field with its tags stripped and
+    /// its entities decoded
+    fn sort_field(html: &str) -> String {
+        let mut text = String::new();
+        let mut in_tag = false;
+        for c in html.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' if in_tag => in_tag = false,
+                c if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
+        text.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&amp;", "&")
+            .trim()
+            .to_string()
+    }
+
+    /// The checksum Anki uses to detect duplicates, the first 8 hex digits of the sha1 of the sort field
+    fn checksum(sort_field: &str) -> i64 {
+        let digest = sha1_smol::Sha1::from(sort_field).digest().bytes();
+        u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]) as i64
+    }
+
+    fn collection_json(decks: &[Deck], now: i64) -> [String; 4] {
+        let conf = json!({
+            "activeDecks": [1], "curDeck": 1, "newSpread": 0, "collapseTime": 1200,
+            "timeLim": 0, "estTimes": true, "dueCounts": true, "curModel": MODEL_ID.to_string(),
+            "nextPos": 1, "sortType": "noteFld", "sortBackwards": false, "addToCur": true
+        });
+        let fields: Vec<_> = ["Front", "Back"]
+            .iter()
+            .enumerate()
+            .map(|(ord, name)| {
+                json!({
+                    "name": name, "ord": ord, "sticky": false, "rtl": false,
+                    "font": "Arial", "size": 20, "media": []
+                })
+            })
+            .collect();
+        let models = json!({
+            MODEL_ID.to_string(): {
+                "id": MODEL_ID, "name": "MoKa Reads Basic", "type": 0, "mod": now, "usn": -1,
+                "sortf": 0, "did": 1, "tags": [], "vers": [],
+                "flds": fields,
+                "tmpls": [{
+                    "name": "Card 1", "ord": 0, "did": null, "bqfmt": "", "bafmt": "",
+                    "qfmt": "{{Front}}", "afmt": "{{FrontSide}}<hr id=answer>{{Back}}"
+                }],
+                "css": ".card { font-family: arial; font-size: 20px; text-align: center; }\npre { text-align: left; }",
+                "latexPre": "\\documentclass[12pt]{article}\n\\special{papersize=3in,5in}\n\\usepackage{amssymb,amsmath}\n\\pagestyle{empty}\n\\setlength{\\parindent}{0in}\n\\begin{document}\n",
+                "latexPost": "\\end{document}",
+                "req": [[0, "any", [0]]]
+            }
+        });
+        let deck_json = |id: i64, name: &str| {
+            json!({
+                "id": id, "name": name, "desc": "", "mod": now, "usn": -1, "conf": 1,
+                "dyn": 0, "collapsed": false, "extendNew": 10, "extendRev": 50,
+                "newToday": [0, 0], "revToday": [0, 0], "lrnToday": [0, 0], "timeToday": [0, 0]
+            })
+        };
+        let mut decks_json = serde_json::Map::new();
+        decks_json.insert("1".to_string(), deck_json(1, "Default"));
+        for deck in decks {
+            let id = deck_id(deck);
+            decks_json.insert(id.to_string(), deck_json(id, &deck.name()));
+        }
+        let dconf = json!({
+            "1": {
+                "id": 1, "name": "Default", "mod": 0, "usn": 0, "maxTaken": 60, "autoplay": true,
+                "timer": 0, "replayq": true, "dyn": false,
+                "new": {"bury": true, "delays": [1, 10], "initialFactor": 2500, "ints": [1, 4, 7],
+                        "order": 1, "perDay": 20, "separate": true},
+                "lapse": {"delays": [10], "leechAction": 0, "leechFails": 8, "minInt": 1, "mult": 0},
+                "rev": {"bury": true, "ease4": 1.3, "fuzz": 0.05, "ivlFct": 1, "maxIvl": 36500,
+                        "minSpace": 1, "perDay": 100}
+            }
+        });
+        [
+            conf.to_string(),
+            models.to_string(),
+            serde_json::Value::Object(decks_json).to_string(),
+            dconf.to_string(),
+        ]
+    }
+
+    fn write_collection(path: &Path, decks: &[Deck]) -> crate::Result<()> {
+        let now = chrono::Utc::now().timestamp();
+        let conn = rusqlite::Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+        let [conf, models, decks_json, dconf] = collection_json(decks, now);
+        conn.execute(
+            "INSERT INTO col VALUES (1, ?1, ?2, ?3, 11, 0, 0, 0, ?4, ?5, ?6, ?7, '{}')",
+            rusqlite::params![now, now * 1000, now * 1000, conf, models, decks_json, dconf],
+        )?;
+
+        // note and card ids are millisecond timestamps in Anki, so they count up from now
+        let mut id = now * 1000;
+        let mut due = 0;
+        for deck in decks {
+            let did = deck_id(deck);
+            for card in &deck.cards {
+                id += 1;
+                due += 1;
+                let front = card.front_html();
+                let sfld = sort_field(&front);
+                let flds = format!("{}\x1f{}", front, card.back_html());
+                let guid = format!("{:x}", fnv(&flds));
+                conn.execute(
+                    "INSERT INTO notes VALUES (?1, ?2, ?3, ?4, -1, ?5, ?6, ?7, ?8, 0, '')",
+                    rusqlite::params![
+                        id,
+                        guid,
+                        MODEL_ID,
+                        now,
+                        format!(" {} {} ", deck.tags(), card.source),
+                        flds,
+                        sfld,
+                        checksum(&sfld)
+                    ],
+                )?;
+                conn.execute(
+                    "INSERT INTO cards VALUES (?1, ?2, ?3, 0, ?4, -1, 0, 0, ?5, 0, 0, 0, 0, 0, 0, 0, 0, '')",
+                    rusqlite::params![id, id, did, now, due],
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the decks as an `.apkg` package that can be imported into Anki
+    pub fn write_apkg(decks: &[Deck], path: &Path) -> crate::Result<()> {
+        let collection = std::env::temp_dir().join(format!(
+            "mokareads-{}-{}.anki2",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let written = write_collection(&collection, decks);
+        let bytes = written.and_then(|_| Ok(std::fs::read(&collection)?));
+        let _ = std::fs::remove_file(&collection);
+        let bytes = bytes?;
+
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(path)?);
+        let options = zip::write::FileOptions::default();
+        zip.start_file("collection.anki2", options)?;
+        zip.write_all(&bytes)?;
+        zip.start_file("media", options)?;
+        zip.write_all(b"{}")?;
+        zip.finish()?;
+        Ok(())
+    }
+}
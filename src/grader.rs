@@ -0,0 +1,413 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::hash::{BuildHasher, Hasher};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use crate::process::{self, Exit, Limits};
+use crate::resources::cheatsheet::Language;
+use crate::resources::exercise::Exercise;
+
+pub use crate::process::{DEFAULT_ENV, DEFAULT_FILE_SIZE_LIMIT};
+
+/// The time a command may run by default before it's killed
+pub const DEFAULT_TIME_LIMIT: Duration = Duration::from_secs(10);
+
+/// What a learner submits for an exercise
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submission {
+    /// The code of a code exercise
+    Code(String),
+    /// The indices of the chosen answers of a quiz
+    Choices(Vec<usize>),
+}
+
+/// The grade of a submission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Grade {
+    /// Every test passed or the right choices were made
+    Passed,
+    /// The submission is wrong, with the output of the toolchain for code
+    Failed(String),
+    /// A command ran past the time limit and was killed
+    TimedOut,
+    /// No runner is configured or installed for the exercise's language
+    Unsupported,
+}
+
+/// A submission that doesn't match its exercise
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GradeError {
+    /// Code was submitted for a quiz or choices for a code exercise
+    Mismatch { exercise: String },
+    /// A chosen answer isn't one of the choices
+    NoSuchChoice { exercise: String, choice: usize },
+}
+
+impl Display for GradeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GradeError::Mismatch { exercise } => {
+                write!(
+                    f,
+                    "the submission doesn't match the kind of exercise `{exercise}`"
+                )
+            }
+            GradeError::NoSuchChoice { exercise, choice } => {
+                write!(f, "exercise `{exercise}` has no choice {choice}")
+            }
+        }
+    }
+}
+
+impl Error for GradeError {}
+
+/// How to build and run a code exercise in a language with a locally installed toolchain
+///
+/// The commands run in order and must all exit successfully. In the program and the arguments,
+/// `{file}` is replaced by the path of the exercise file and `{dir}` by its temporary directory.
+///
+/// The exercise file is the harness with `{submission}` replaced by the submitted code and
+/// `{tests}` by the hidden tests. When the harness also contains `{sentinel}`, it's replaced by a
+/// random line the harness must print once the tests finish, and the submission only passes if
+/// the last command's output contains it, so a submission can't exit early to skip the tests.
+#[derive(Debug, Clone)]
+pub struct Runner {
+    pub extension: String,
+    pub commands: Vec<(String, Vec<String>)>,
+    pub harness: String,
+    pub entry_point: Option<(String, String)>,
+}
+
+impl Runner {
+    pub fn new(extension: &str) -> Self {
+        Self {
+            extension: extension.to_string(),
+            commands: Vec::new(),
+            harness: "{submission}\n{tests}".to_string(),
+            entry_point: None,
+        }
+    }
+    /// Adds a command to run after the previous ones
+    pub fn with_command(mut self, program: &str, args: &[&str]) -> Self {
+        self.commands.push((
+            program.to_string(),
+            args.iter().map(|x| x.to_string()).collect(),
+        ));
+        self
+    }
+    /// Sets the harness the submission and the tests are placed in
+    pub fn with_harness(mut self, harness: &str) -> Self {
+        self.harness = harness.to_string();
+        self
+    }
+    /// Renames the entry point of the tests from `from` to `to`, so the harness can call them
+    pub fn with_entry_point(mut self, from: &str, to: &str) -> Self {
+        self.entry_point = Some((from.to_string(), to.to_string()));
+        self
+    }
+
+    /// Builds the exercise file from the submission and the tests
+    fn source(&self, code: &str, tests: &str, sentinel: &str) -> String {
+        let tests = match &self.entry_point {
+            Some((from, to)) => tests.replace(from, to),
+            None => tests.to_string(),
+        };
+        // the placeholders are replaced in one pass, so a submission containing one can't
+        // smuggle in the sentinel
+        let mut source = String::new();
+        let mut rest = self.harness.as_str();
+        while let Some(start) = rest.find('{') {
+            source.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let (value, len) = if rest.starts_with("{submission}") {
+                (code, "{submission}".len())
+            } else if rest.starts_with("{tests}") {
+                (tests.as_str(), "{tests}".len())
+            } else if rest.starts_with("{sentinel}") {
+                (sentinel, "{sentinel}".len())
+            } else {
+                ("{", 1)
+            };
+            source.push_str(value);
+            rest = &rest[len..];
+        }
+        source.push_str(rest);
+        source
+    }
+}
+
+/// The Rust harness, which calls the tests' `main` whether it returns `()` or a `Result`
+const RUST_HARNESS: &str = r#"{submission}
+{tests}
+
+trait GraderOutcome {
+    fn passed(self) -> bool;
+}
+
+impl GraderOutcome for () {
+    fn passed(self) -> bool {
+        true
+    }
+}
+
+impl<E: std::fmt::Debug> GraderOutcome for Result<(), E> {
+    fn passed(self) -> bool {
+        match self {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("Error: {e:?}");
+                false
+            }
+        }
+    }
+}
+
+fn main() {
+    if !GraderOutcome::passed(grader_tests()) {
+        std::process::exit(1);
+    }
+    println!("{sentinel}");
+}
+"#;
+
+const PYTHON_HARNESS: &str = "{submission}\n{tests}\nprint(\"{sentinel}\")\n";
+
+const GO_HARNESS: &str =
+    "{submission}\n{tests}\n\nfunc main() {\n\tgraderTests()\n\tprintln(\"{sentinel}\")\n}\n";
+
+const ZIG_HARNESS: &str = r#"{submission}
+{tests}
+
+pub fn main() !void {
+    const result: anyerror!void = graderTests();
+    try result;
+    @import("std").debug.print("{sentinel}\n", .{});
+}
+"#;
+
+/// Used to tell the gradings apart, in their directory names and sentinels
+static GRADE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// # Grader
+///
+/// Grades submissions to [`Exercise`]s. Quizzes are checked against their answers, and code is
+/// placed in a harness with the hidden tests and run with the locally installed toolchains, by
+/// default:
+/// - Rust: `rustc` then the built binary, with the tests' `fn main` renamed to `grader_tests`
+/// - Python: `python`, with the tests run after the submission
+/// - Go: `go run`, with the tests' `func main` renamed to `graderTests`
+/// - Zig: `zig run`, with the tests' `fn main` renamed to `graderTests`
+///
+/// Each harness prints a sentinel once the tests finish, so exiting early fails the submission.
+///
+/// Every submission runs in its own temporary directory, which has a random name, is only
+/// accessible to the current user and is removed afterwards. Each command runs there with stdin
+/// closed and an environment cleared down to [`DEFAULT_ENV`], and on Unix without any other
+/// inherited file descriptors, in its own process group that's killed as a whole once it runs
+/// past the time limit, with limits on CPU time, the size of written files, core dumps and
+/// optionally memory.
+///
+/// This keeps submissions away from each other, from the grader's own files and from other users,
+/// and bounds the time and resources they use. It doesn't restrict what the current user may do
+/// otherwise: a submission can still read the rest of the file system, use the network and find
+/// the sentinel in its own source, so the grader should run as a dedicated user, or inside a
+/// container where untrusted code is graded.
+#[derive(Debug, Clone)]
+pub struct Grader {
+    runners: HashMap<Language, Runner>,
+    limits: Limits,
+    temp_root: PathBuf,
+}
+
+impl Default for Grader {
+    fn default() -> Self {
+        let mut runners = HashMap::new();
+        runners.insert(
+            Language::Rust,
+            Runner::new("rs")
+                .with_command(
+                    "rustc",
+                    &["--edition", "2021", "-o", "{dir}/exercise", "{file}"],
+                )
+                .with_command("{dir}/exercise", &[])
+                .with_harness(RUST_HARNESS)
+                .with_entry_point("fn main(", "fn grader_tests("),
+        );
+        runners.insert(
+            Language::Python,
+            Runner::new("py")
+                .with_command("python", &["{file}"])
+                .with_harness(PYTHON_HARNESS),
+        );
+        runners.insert(
+            Language::Go,
+            Runner::new("go")
+                .with_command("go", &["run", "{file}"])
+                .with_harness(GO_HARNESS)
+                .with_entry_point("func main(", "func graderTests("),
+        );
+        runners.insert(
+            Language::Zig,
+            Runner::new("zig")
+                .with_command("zig", &["run", "{file}"])
+                .with_harness(ZIG_HARNESS)
+                .with_entry_point("pub fn main(", "fn graderTests("),
+        );
+        Self {
+            runners,
+            limits: Limits::new(DEFAULT_TIME_LIMIT),
+            temp_root: std::env::temp_dir(),
+        }
+    }
+}
+
+impl Grader {
+    /// Sets the runner used for a language
+    pub fn with_runner(mut self, lang: Language, runner: Runner) -> Self {
+        self.runners.insert(lang, runner);
+        self
+    }
+    /// Sets the time each command may run before it's killed
+    pub fn with_time_limit(mut self, time_limit: Duration) -> Self {
+        self.limits.time = time_limit;
+        self
+    }
+    /// Limits the address space of each command, in bytes, which is unlimited by default
+    pub fn with_memory_limit(mut self, memory_limit: u64) -> Self {
+        self.limits.memory = Some(memory_limit);
+        self
+    }
+    /// Sets the largest file each command may write, in bytes
+    pub fn with_file_size_limit(mut self, file_size_limit: u64) -> Self {
+        self.limits.file_size = file_size_limit;
+        self
+    }
+    /// Sets the environment variables passed through to the commands
+    pub fn with_env(mut self, env: &[&str]) -> Self {
+        self.limits.env = env.iter().map(|x| x.to_string()).collect();
+        self
+    }
+    /// Sets the directory the temporary grading directories are created in
+    pub fn with_temp_root(mut self, temp_root: PathBuf) -> Self {
+        self.temp_root = temp_root;
+        self
+    }
+
+    /// Grades a submission to the exercise
+    pub fn grade(&self, exercise: &Exercise, submission: &Submission) -> crate::Result<Grade> {
+        match submission {
+            Submission::Choices(choices) if exercise.is_quiz() => {
+                Ok(Self::grade_choices(exercise, choices)?)
+            }
+            Submission::Code(code) if !exercise.is_quiz() => self.grade_code(exercise, code),
+            _ => Err(GradeError::Mismatch {
+                exercise: exercise.slug.clone(),
+            }
+            .into()),
+        }
+    }
+
+    /// Checks that exactly the right choices were made, in any order
+    fn grade_choices(exercise: &Exercise, choices: &[usize]) -> Result<Grade, GradeError> {
+        if let Some(choice) = choices.iter().find(|x| **x >= exercise.choices().len()) {
+            return Err(GradeError::NoSuchChoice {
+                exercise: exercise.slug.clone(),
+                choice: *choice,
+            });
+        }
+        let mut chosen = choices.to_vec();
+        chosen.sort_unstable();
+        chosen.dedup();
+        let mut answers = exercise.answers().to_vec();
+        answers.sort_unstable();
+        answers.dedup();
+        if chosen == answers {
+            Ok(Grade::Passed)
+        } else {
+            Ok(Grade::Failed(format!(
+                "{} of the {} right choices were made, with {} wrong",
+                chosen.iter().filter(|x| answers.contains(x)).count(),
+                answers.len(),
+                chosen.iter().filter(|x| !answers.contains(x)).count()
+            )))
+        }
+    }
+
+    fn grade_code(&self, exercise: &Exercise, code: &str) -> crate::Result<Grade> {
+        let runner = match self.runners.get(&exercise.language()) {
+            Some(runner) => runner,
+            None => return Ok(Grade::Unsupported),
+        };
+
+        let n = GRADE_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_usize(n);
+        let sentinel = format!("mokareads-tests-finished-{:016x}", hasher.finish());
+
+        let dir = process::private_dir(
+            &self.temp_root,
+            &format!("mokareads-exercise-{}-{n}", std::process::id()),
+        )?;
+        let file = dir.join(format!("exercise.{}", runner.extension));
+        let source = runner.source(code, exercise.tests(), &sentinel);
+        let grade = std::fs::write(&file, source)
+            .map_err(|e| e.into())
+            .and_then(|_| self.run(runner, &dir, &file, &sentinel));
+        // a leftover directory in the temp root isn't worth losing the grade over
+        let _ = std::fs::remove_dir_all(&dir);
+        grade
+    }
+
+    fn run(
+        &self,
+        runner: &Runner,
+        dir: &Path,
+        file: &Path,
+        sentinel: &str,
+    ) -> crate::Result<Grade> {
+        let dir_str = dir.to_string_lossy();
+        let file_str = file.to_string_lossy();
+        let fill = |x: &str| x.replace("{dir}", &dir_str).replace("{file}", &file_str);
+        let mut output = String::new();
+        for (i, (program, args)) in runner.commands.iter().enumerate() {
+            let args: Vec<String> = args.iter().map(|x| fill(x)).collect();
+            let exit = process::run(
+                &fill(program),
+                &args,
+                dir,
+                &format!("command-{i}"),
+                &self.limits,
+            );
+            match exit {
+                Ok(Exit::Exited {
+                    success,
+                    stdout,
+                    stderr,
+                }) => {
+                    output = format!("{stderr}{stdout}");
+                    if !success {
+                        return Ok(Grade::Failed(output.trim().to_string()));
+                    }
+                }
+                Ok(Exit::TimedOut) => return Ok(Grade::TimedOut),
+                Err(e) if e.kind() == ErrorKind::NotFound && i == 0 => {
+                    return Ok(Grade::Unsupported)
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        if runner.harness.contains("{sentinel}") && !output.contains(sentinel) {
+            return Ok(Grade::Failed(
+                format!("the program exited before the tests finished\n{output}")
+                    .trim()
+                    .to_string(),
+            ));
+        }
+        Ok(Grade::Passed)
+    }
+}
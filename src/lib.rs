@@ -8,11 +8,19 @@
 pub mod api;
 /// Awesome Lists of various topics in GitHub
 pub mod awesome_lists;
+/// Exporting cheatsheets as flashcards for spaced repetition apps
+pub mod flashcards;
+/// Grading exercise submissions with the local toolchains
+pub mod grader;
 #[cfg(feature = "experimental")]
 /// This is still experimental
 pub mod latex;
+/// Running toolchain commands with time and resource limits
+mod process;
 /// The different MoKa Reads Resources
 pub mod resources;
+/// Compile checking of the code examples in resources
+pub mod snippets;
 
 pub use rss::{Channel, Item};
 
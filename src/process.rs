@@ -0,0 +1,248 @@
+use std::collections::hash_map::RandomState;
+use std::fs::{DirBuilder, File};
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// The environment variables passed through by default, which the toolchains need to find
+/// themselves and their caches
+pub const DEFAULT_ENV: [&str; 16] = [
+    "PATH",
+    "HOME",
+    "LANG",
+    "SYSTEMROOT",
+    "RUSTUP_HOME",
+    "RUSTUP_TOOLCHAIN",
+    "CARGO_HOME",
+    "GOROOT",
+    "GOPATH",
+    "GOCACHE",
+    "GOMODCACHE",
+    "ZIG_GLOBAL_CACHE_DIR",
+    "ZIG_LIB_DIR",
+    "PYENV_ROOT",
+    "PYENV_VERSION",
+    "VIRTUAL_ENV",
+];
+
+/// The largest file a command may write by default, in bytes
+pub const DEFAULT_FILE_SIZE_LIMIT: u64 = 64 * 1024 * 1024;
+
+/// The limits a command runs under
+///
+/// The command is started in a private directory (see [`private_dir`]) with stdin closed, a
+/// cleared environment that only keeps the allowed variables and `TMPDIR` pointing at the
+/// directory. On Unix it inherits no file descriptors besides stdin, stdout and stderr, gets its
+/// own process group, which is killed as a whole once it runs past the time limit, and resource
+/// limits on CPU time, file size, core dumps and optionally address space.
+#[derive(Debug, Clone)]
+pub(crate) struct Limits {
+    pub time: Duration,
+    pub memory: Option<u64>,
+    pub file_size: u64,
+    pub env: Vec<String>,
+}
+
+impl Limits {
+    pub fn new(time: Duration) -> Self {
+        Self {
+            time,
+            memory: None,
+            file_size: DEFAULT_FILE_SIZE_LIMIT,
+            env: DEFAULT_ENV.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+}
+
+/// How a command ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Exit {
+    /// The command exited, with its stdout and stderr
+    Exited {
+        success: bool,
+        stdout: String,
+        stderr: String,
+    },
+    /// The command ran past the time limit and was killed
+    TimedOut,
+}
+
+/// Creates a new directory in `root` that only the current user can access, named after
+/// `prefix` and a random suffix so other users can neither guess it nor create it in advance
+pub(crate) fn private_dir(root: &Path, prefix: &str) -> io::Result<PathBuf> {
+    std::fs::create_dir_all(root)?;
+    let state = RandomState::new();
+    let mut builder = DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    let mut attempt = 0;
+    loop {
+        let mut hasher = state.build_hasher();
+        hasher.write_u32(attempt);
+        let dir = root.join(format!("{prefix}-{:016x}", hasher.finish()));
+        // unlike `create_dir_all`, this fails when the directory already exists
+        match builder.create(&dir) {
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists && attempt < 8 => attempt += 1,
+            result => return result.map(|_| dir),
+        }
+    }
+}
+
+/// Runs a command in `dir` under the limits, naming its output files after `name`
+pub(crate) fn run(
+    program: &str,
+    args: &[String],
+    dir: &Path,
+    name: &str,
+    limits: &Limits,
+) -> io::Result<Exit> {
+    // the output goes to files so a chatty command can't fill a pipe and hang
+    let stdout = dir.join(format!("{name}.stdout"));
+    let stderr = dir.join(format!("{name}.stderr"));
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(File::create(&stdout)?)
+        .stderr(File::create(&stderr)?)
+        .env_clear()
+        .env("TMPDIR", dir);
+    for key in &limits.env {
+        if let Some(value) = std::env::var_os(key) {
+            command.env(key, value);
+        }
+    }
+    #[cfg(unix)]
+    restrict(&mut command, limits);
+    let mut child = command.spawn()?;
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() >= limits.time {
+            kill(&mut child)?;
+            child.wait()?;
+            return Ok(Exit::TimedOut);
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    };
+    Ok(Exit::Exited {
+        success: status.success(),
+        stdout: std::fs::read_to_string(&stdout)?,
+        stderr: std::fs::read_to_string(&stderr)?,
+    })
+}
+
+/// Puts the command in its own process group and sets its resource limits
+#[cfg(unix)]
+fn restrict(command: &mut Command, limits: &Limits) {
+    use std::os::unix::process::CommandExt;
+
+    let cpu = limits.time.as_secs() + 1;
+    let memory = limits.memory;
+    let file_size = limits.file_size;
+    command.process_group(0);
+    // SAFETY: only async-signal-safe calls are made between fork and exec
+    unsafe {
+        command.pre_exec(move || {
+            set_limit(libc::RLIMIT_CPU, cpu)?;
+            set_limit(libc::RLIMIT_FSIZE, file_size)?;
+            set_limit(libc::RLIMIT_CORE, 0)?;
+            if let Some(memory) = memory {
+                set_limit(libc::RLIMIT_AS, memory)?;
+            }
+            close_on_exec();
+            Ok(())
+        });
+    }
+}
+
+#[cfg(all(unix, any(target_os = "linux", target_os = "android")))]
+type Resource = libc::__rlimit_resource_t;
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "android"))))]
+type Resource = libc::c_int;
+
+/// Lowers both the soft and the hard limit, so the command can't raise it again
+#[cfg(unix)]
+fn set_limit(resource: Resource, value: u64) -> io::Result<()> {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: `limit` is a valid rlimit for both calls
+    unsafe {
+        if libc::getrlimit(resource, &mut limit) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let value = (value as libc::rlim_t).min(limit.rlim_max);
+        limit.rlim_cur = value;
+        limit.rlim_max = value;
+        if libc::setrlimit(resource, &limit) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Marks every file descriptor above stderr close-on-exec, so the command doesn't inherit the
+/// ones opened without it. They're not closed right away, as the pipe reporting a failed exec
+/// back to the parent is among them.
+#[cfg(unix)]
+fn close_on_exec() {
+    // SAFETY: close_range and fcntl have no memory safety requirements
+    unsafe {
+        #[cfg(target_os = "linux")]
+        if libc::syscall(
+            libc::SYS_close_range,
+            3 as libc::c_uint,
+            libc::c_uint::MAX,
+            libc::CLOSE_RANGE_CLOEXEC,
+        ) == 0
+        {
+            return;
+        }
+        // without close_range, every descriptor up to the limit on open files is checked
+        let mut limit = libc::rlimit {
+            rlim_cur: 0,
+            rlim_max: 0,
+        };
+        let max = if libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) == 0 {
+            limit.rlim_cur.min(65536) as libc::c_int
+        } else {
+            1024
+        };
+        for fd in 3..max {
+            let flags = libc::fcntl(fd, libc::F_GETFD);
+            if flags >= 0 && flags & libc::FD_CLOEXEC == 0 {
+                libc::fcntl(fd, libc::F_SETFD, flags | libc::FD_CLOEXEC);
+            }
+        }
+    }
+}
+
+/// Kills the command's whole process group, or just the command where there are none
+fn kill(child: &mut Child) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        // the command hasn't been waited on yet, so its group can't have been reused
+        // SAFETY: killpg has no memory safety requirements
+        if unsafe { libc::killpg(child.id() as libc::pid_t, libc::SIGKILL) } != 0 {
+            let e = io::Error::last_os_error();
+            // the group is already gone when the command exited on its own in the meantime
+            if e.raw_os_error() != Some(libc::ESRCH) {
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+    #[cfg(not(unix))]
+    child.kill()
+}
@@ -1,20 +1,45 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
 use std::str::FromStr;
 
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 
-use article::Article;
+use article::{Article, SeriesError};
 use cheatsheet::Cheatsheet;
+use course::{Course, CourseError, Lesson};
+use exercise::Exercise;
+use glossary::{Glossary, Term};
 use guide::Guide;
+use locale::Alternate;
 
-use crate::resources::cheatsheet::{get_lang_map, Language};
+use crate::resources::cheatsheet::{Language, Level};
+use crate::resources::version::{Version, VersionPolicy};
 
 /// MoKa Reads Article
 pub mod article;
 /// MoKa Reads Cheatsheets
 pub mod cheatsheet;
+/// MoKa Reads Courses
+pub mod course;
+/// MoKa Reads Exercises
+pub mod exercise;
+/// MoKa Reads Glossary
+pub mod glossary;
 /// MoKa Reads How to Guides
 pub mod guide;
+/// Learning paths across cheatsheet levels
+pub mod learning_path;
+/// Human-language locales of resources and their translations
+pub mod locale;
+/// "You might also like" recommendations between resources
+pub mod recommender;
+/// Language versions targeted by resources
+pub mod version;
+/// Searching through the resources
+pub mod searcher;
+
+pub use searcher::{SearchMetadata, Searcher};
 
 /// Parses MarkDown to a type
 pub trait Parser {
@@ -32,13 +57,140 @@ pub trait Parser {
         Self: Sized;
 }
 
+/// # Resource
+///
+/// What every kind of resource has in common, so the cacher, the [`Searcher`] and the
+/// [`Recommender`](recommender::Recommender) can handle any of them alike. A new kind of
+/// resource only has to implement it to be searchable and recommended.
+pub trait Resource {
+    /// Identifies the resource among every resource of the cacher
+    fn id(&self) -> String {
+        self.link_short()
+    }
+    fn resource_type(&self) -> ResourceType;
+    fn title(&self) -> String;
+    fn slug(&self) -> &str;
+    fn author(&self) -> String {
+        String::new()
+    }
+    /// The languages the resource is about
+    fn languages(&self) -> Vec<Language> {
+        Vec::new()
+    }
+    fn tags(&self) -> Vec<String> {
+        Vec::new()
+    }
+    fn description(&self) -> String {
+        String::new()
+    }
+    /// The content of the resource, markdown when parsed raw and html otherwise
+    fn content(&self) -> &str {
+        ""
+    }
+    /// The level of the resource, for the resources that have one
+    fn difficulty(&self) -> Option<Level> {
+        None
+    }
+    /// The date the resource was published (YYYY-MM-DD), for the resources that have one
+    fn published(&self) -> Option<String> {
+        None
+    }
+    /// Whether the resource is a draft, hidden until it's unmarked
+    fn is_draft(&self) -> bool {
+        false
+    }
+    /// The time the resource is scheduled to be published at, for the resources that have one
+    fn publish_at(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+    /// Whether the resource is public at the time, neither a draft nor scheduled after it
+    fn is_published_at(&self, at: DateTime<Utc>) -> bool {
+        !self.is_draft() && self.publish_at().is_none_or(|x| x <= at)
+    }
+    /// The human language the resource is written in, normalised by [`locale::normalize`]
+    fn locale(&self) -> String {
+        locale::DEFAULT_LOCALE.to_string()
+    }
+    /// Shared by the translations of the resource, which are resources of the same type in other locales
+    fn translation_key(&self) -> String {
+        self.id()
+    }
+    /// The link to the resource on the website
+    fn link(&self) -> String {
+        format!("https://moka-reads.mkproj.com{}", self.link_short())
+    }
+    /// The link to the resource relative to the website
+    fn link_short(&self) -> String;
+    fn as_search_meta(&self) -> SearchMetadata;
+}
+
 /// A type to store all different resources with a time of update to show when resources were last cached
+///
+/// The cacher keeps hash indices over the `(locale, slug)` pairs of the articles, the course,
+/// exercise and glossary term slugs and the `(Language, slug)` pairs of the cheatsheets, these
+/// aren't serialized and are rebuilt whenever a cacher is deserialized.
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[serde(from = "CacherData")]
 pub struct Cacher {
     updated_at: String,
     articles: Vec<Article>,
     cheatsheets: Vec<Cheatsheet>,
     guides: Vec<Guide>,
+    courses: Vec<Course>,
+    exercises: Vec<Exercise>,
+    glossary: Vec<Term>,
+    #[serde(skip)]
+    /// By locale and slug, as translations can share a slug
+    article_index: HashMap<(String, String), usize>,
+    #[serde(skip)]
+    /// By normalised language (see [`cheatsheet::normalize_lang`]) and slug
+    cheatsheet_index: HashMap<(String, String), usize>,
+    #[serde(skip)]
+    course_index: HashMap<String, usize>,
+    #[serde(skip)]
+    exercise_index: HashMap<String, usize>,
+    #[serde(skip)]
+    term_index: HashMap<String, usize>,
+    /// Includes the unpublished resources in the searcher, recommender and feeds
+    #[serde(skip)]
+    preview: bool,
+}
+
+/// The serialized fields of a [`Cacher`]
+#[derive(Deserialize)]
+struct CacherData {
+    updated_at: String,
+    articles: Vec<Article>,
+    cheatsheets: Vec<Cheatsheet>,
+    guides: Vec<Guide>,
+    #[serde(default)]
+    courses: Vec<Course>,
+    #[serde(default)]
+    exercises: Vec<Exercise>,
+    #[serde(default)]
+    glossary: Vec<Term>,
+}
+
+impl From<CacherData> for Cacher {
+    fn from(value: CacherData) -> Self {
+        let mut cacher = Self {
+            updated_at: value.updated_at,
+            articles: value.articles,
+            cheatsheets: value.cheatsheets,
+            guides: value.guides,
+            courses: value.courses,
+            exercises: value.exercises,
+            glossary: value.glossary,
+            article_index: HashMap::new(),
+            cheatsheet_index: HashMap::new(),
+            course_index: HashMap::new(),
+            exercise_index: HashMap::new(),
+            term_index: HashMap::new(),
+            preview: false,
+        };
+        cacher.reindex();
+        cacher
+    }
 }
 
 #[derive(Debug, Copy, Clone, Deserialize, Serialize, Hash, Eq, PartialEq, Ord, PartialOrd)]
@@ -46,6 +198,9 @@ pub enum ResourceType {
     Article,
     Cheatsheet,
     Guide,
+    Course,
+    Exercise,
+    Glossary,
 }
 
 impl FromStr for ResourceType {
@@ -55,158 +210,428 @@ impl FromStr for ResourceType {
             "article" => Ok(ResourceType::Article),
             "cheatsheet" => Ok(ResourceType::Cheatsheet),
             "guide" => Ok(ResourceType::Guide),
+            "course" => Ok(ResourceType::Course),
+            "exercise" => Ok(ResourceType::Exercise),
+            "glossary" => Ok(ResourceType::Glossary),
             _ => Err(()),
         }
     }
 }
 
-/// Metadata for a search result
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct SearchMetadata {
-    title: String,
-    ty: ResourceType,
-    link: String,
-    lang: String,
-}
+impl Cacher {
+    pub fn new(articles: Vec<Article>, cheatsheets: Vec<Cheatsheet>, guides: Vec<Guide>) -> Self {
+        let updated_at = chrono::Utc::now().to_string();
+        CacherData {
+            updated_at,
+            articles,
+            cheatsheets,
+            guides,
+            courses: Vec::new(),
+            exercises: Vec::new(),
+            glossary: Vec::new(),
+        }
+        .into()
+    }
+    /// Sets the courses of the cacher
+    pub fn with_courses(mut self, courses: Vec<Course>) -> Self {
+        self.courses = courses;
+        self.reindex();
+        self
+    }
+    /// Sets the exercises of the cacher
+    pub fn with_exercises(mut self, exercises: Vec<Exercise>) -> Self {
+        self.exercises = exercises;
+        self.reindex();
+        self
+    }
+    /// Sets the glossary terms of the cacher
+    pub fn with_glossary(mut self, terms: Vec<Term>) -> Self {
+        self.glossary = terms;
+        self.reindex();
+        self
+    }
+
+    /// Turns the preview mode on or off, in preview mode the drafts and the resources scheduled
+    /// later are searched, recommended and put in the feeds like the published ones
+    pub fn with_preview(mut self, preview: bool) -> Self {
+        self.preview = preview;
+        self
+    }
+    pub fn is_preview(&self) -> bool {
+        self.preview
+    }
 
-impl SearchMetadata {
-    pub fn new(title: String, ty: ResourceType, link: String, lang: String) -> Self {
-        Self {
-            title,
-            ty,
-            link,
-            lang,
+    /// Rebuilds the hash indices, if a slug appears more than once the first resource wins
+    fn reindex(&mut self) {
+        self.article_index.clear();
+        for (i, article) in self.articles.iter().enumerate() {
+            self.article_index
+                .entry((article.locale(), article.slug.clone()))
+                .or_insert(i);
+        }
+        self.cheatsheet_index.clear();
+        for (i, cheatsheet) in self.cheatsheets.iter().enumerate() {
+            self.cheatsheet_index
+                .entry((cheatsheet.lang_key(), cheatsheet.slug.clone()))
+                .or_insert(i);
+        }
+        self.course_index.clear();
+        for (i, course) in self.courses.iter().enumerate() {
+            self.course_index.entry(course.slug.clone()).or_insert(i);
+        }
+        self.exercise_index.clear();
+        for (i, exercise) in self.exercises.iter().enumerate() {
+            self.exercise_index
+                .entry(exercise.slug.clone())
+                .or_insert(i);
+        }
+        self.term_index.clear();
+        for (i, term) in self.glossary.iter().enumerate() {
+            self.term_index.entry(term.slug.clone()).or_insert(i);
         }
     }
-}
 
-/// Hashmaps for quick navigation
-///
-/// A user will be able to search for something under the following conditions:
-/// - Language of Focus
-/// - Title of Resource
-/// - Resource Type
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct Searcher {
-    langs: HashMap<Language, Vec<SearchMetadata>>,
-    titles: HashMap<String, Vec<SearchMetadata>>,
-    rty: HashMap<ResourceType, Vec<SearchMetadata>>,
-}
+    /// Finds an article by its slug, the one in the default locale in `O(1)` when there is one,
+    /// otherwise the first article with the slug in any locale in `O(n)`
+    pub fn article(&self, slug: &str) -> Option<&Article> {
+        self.localized_article(slug, locale::DEFAULT_LOCALE)
+            .or_else(|| self.articles.iter().find(|x| x.slug == slug))
+    }
+    /// Finds an article by its slug in exactly the locale in `O(1)`
+    pub fn localized_article(&self, slug: &str, locale: &str) -> Option<&Article> {
+        self.article_index
+            .get(&(locale::normalize(locale), slug.to_string()))
+            .map(|i| &self.articles[*i])
+    }
+    /// The cheatsheets of a language targeting at least the `min` version, sorted by level
+    pub fn cheatsheets_since(&self, lang: Language, min: &Version) -> Vec<&Cheatsheet> {
+        let mut cheatsheets: Vec<&Cheatsheet> = self
+            .cheatsheets
+            .iter()
+            .filter(|x| x.language() == lang && x.targets_at_least(min))
+            .collect();
+        cheatsheets.sort_by_key(|x| x.level());
+        cheatsheets
+    }
+    /// The cheatsheets targeting a version or edition older than the policy's minimum
+    pub fn stale_cheatsheets(&self, policy: &VersionPolicy) -> Vec<&Cheatsheet> {
+        self.cheatsheets.iter().filter(|x| policy.is_stale(x)).collect()
+    }
 
-impl Searcher {
-    pub fn new(cacher: &Cacher) -> Self {
-        let mut langs = HashMap::new();
-        let mut titles = HashMap::new();
-        let mut rty = HashMap::new();
+    /// Finds a cheatsheet by its language and slug in `O(1)`, the language is either
+    /// a [`Language`] or the name of one without a variant like `"haskell"`
+    pub fn cheatsheet(&self, lang: impl Display, slug: &str) -> Option<&Cheatsheet> {
+        let key = cheatsheet_key(lang, slug);
+        self.cheatsheet_index
+            .get(&key)
+            .map(|i| &self.cheatsheets[*i])
+    }
 
-        // Collect unique titles from various sources
-        let mut titles_chain: Vec<String> = cacher
+    /// Inserts an article, returning the article it replaced with the same locale and slug
+    pub fn insert_article(&mut self, article: Article) -> Option<Article> {
+        let key = (article.locale(), article.slug.clone());
+        match self.article_index.get(&key) {
+            Some(i) => Some(std::mem::replace(&mut self.articles[*i], article)),
+            None => {
+                self.article_index.insert(key, self.articles.len());
+                self.articles.push(article);
+                None
+            }
+        }
+    }
+    /// Inserts a cheatsheet, returning the cheatsheet it replaced with the same language and slug
+    pub fn insert_cheatsheet(&mut self, cheatsheet: Cheatsheet) -> Option<Cheatsheet> {
+        let key = (cheatsheet.lang_key(), cheatsheet.slug.clone());
+        match self.cheatsheet_index.get(&key) {
+            Some(i) => Some(std::mem::replace(&mut self.cheatsheets[*i], cheatsheet)),
+            None => {
+                self.cheatsheet_index.insert(key, self.cheatsheets.len());
+                self.cheatsheets.push(cheatsheet);
+                None
+            }
+        }
+    }
+    /// Removes the article [`Cacher::article`] finds by the slug
+    pub fn remove_article(&mut self, slug: &str) -> Option<Article> {
+        let locale = self.article(slug)?.locale();
+        self.remove_localized_article(slug, &locale)
+    }
+    /// Removes an article by its slug and locale
+    pub fn remove_localized_article(&mut self, slug: &str, locale: &str) -> Option<Article> {
+        let i = self
+            .article_index
+            .remove(&(locale::normalize(locale), slug.to_string()))?;
+        let article = self.articles.remove(i);
+        self.reindex();
+        Some(article)
+    }
+    /// Removes a cheatsheet by its language and slug
+    pub fn remove_cheatsheet(&mut self, lang: impl Display, slug: &str) -> Option<Cheatsheet> {
+        let key = cheatsheet_key(lang, slug);
+        let i = self.cheatsheet_index.remove(&key)?;
+        let cheatsheet = self.cheatsheets.remove(i);
+        self.reindex();
+        Some(cheatsheet)
+    }
+
+    /// The articles of a series in the default locale, see [`Cacher::series_in`]
+    pub fn series(&self, series: &str) -> Vec<&Article> {
+        self.series_in(series, locale::DEFAULT_LOCALE)
+    }
+    /// The articles of a series in the locale ordered by their `series_order`,
+    /// articles without one come last
+    pub fn series_in(&self, series: &str, locale: &str) -> Vec<&Article> {
+        let locale = locale::normalize(locale);
+        let mut articles: Vec<&Article> = self
             .articles
             .iter()
-            .map(|x| x.title())
-            .chain(cacher.cheatsheets.iter().map(|x| x.title()))
-            .chain(cacher.guides.iter().map(|x| x.unslug.clone()))
+            .filter(|x| x.series() == Some(series) && x.locale() == locale)
             .collect();
-        titles_chain.sort();
-        titles_chain.dedup();
-
-        // Generate search metadata for different languages
-        let lang_vec = Language::all_variants();
-        let lang_map_cheatsheets = get_lang_map(&cacher.cheatsheets);
-
-        for lang in lang_vec {
-            let cheatsheet = lang_map_cheatsheets.get(&lang).unwrap();
-            let mut search_metas: Vec<SearchMetadata> =
-                cheatsheet.iter().map(|x| x.as_search_meta()).collect();
-
-            search_metas.extend(
-                cacher
-                    .articles
-                    .iter()
-                    .filter(|x| x.lang_in_tag(lang))
-                    .map(|x| x.as_search_meta()),
-            );
-            langs.insert(lang, search_metas);
+        articles.sort_by_key(|x| x.series_order().unwrap_or(u32::MAX));
+        articles
+    }
+    /// The part of the article's series before it, `None` for the first part or an article outside a series
+    pub fn previous_in_series(&self, article: &Article) -> Option<&Article> {
+        let series = self.series_in(article.series()?, &article.locale());
+        let position = series.iter().position(|x| x.slug == article.slug)?;
+        series.get(position.checked_sub(1)?).copied()
+    }
+    /// The part of the article's series after it, `None` for the last part or an article outside a series
+    pub fn next_in_series(&self, article: &Article) -> Option<&Article> {
+        let series = self.series_in(article.series()?, &article.locale());
+        let position = series.iter().position(|x| x.slug == article.slug)?;
+        series.get(position + 1).copied()
+    }
+    /// Returns every article without an order or with order 0, repeated order and range of missing
+    /// parts of each series, the series of each locale being separate and sorted by locale and name
+    pub fn validate_series(&self) -> Vec<SeriesError> {
+        let mut all_series: BTreeMap<(String, &str), Vec<&Article>> = BTreeMap::new();
+        for article in &self.articles {
+            if let Some(series) = article.series() {
+                all_series
+                    .entry((article.locale(), series))
+                    .or_default()
+                    .push(article);
+            }
         }
 
-        // Generate search metadata for titles
-        for title in &titles_chain {
-            let mut search_metas = Vec::new();
-
-            search_metas.extend(
-                cacher
-                    .cheatsheets
-                    .iter()
-                    .filter(|x| &x.title() == title)
-                    .map(|x| x.as_search_meta()),
-            );
-            search_metas.extend(
-                cacher
-                    .articles
-                    .iter()
-                    .filter(|x| &x.title() == title)
-                    .map(|x| x.as_search_meta()),
-            );
-            search_metas.extend(
-                cacher
-                    .guides
-                    .iter()
-                    .filter(|x| &x.unslug == title)
-                    .map(|x| x.as_search_meta()),
-            );
-
-            titles.insert(title.clone(), search_metas);
+        let mut errors = Vec::new();
+        for ((locale, series), articles) in all_series {
+            let mut orders: BTreeMap<u32, Vec<String>> = BTreeMap::new();
+            for article in articles {
+                match article.series_order() {
+                    Some(order) => orders.entry(order).or_default().push(article.slug.clone()),
+                    None => errors.push(SeriesError::MissingOrder {
+                        series: series.to_string(),
+                        locale: locale.clone(),
+                        article: article.slug.clone(),
+                    }),
+                }
+            }
+            if let Some(articles) = orders.remove(&0) {
+                errors.extend(articles.into_iter().map(|article| SeriesError::ZeroOrder {
+                    series: series.to_string(),
+                    locale: locale.clone(),
+                    article,
+                }));
+            }
+            let mut next = 1;
+            for (order, articles) in orders {
+                if order > next {
+                    errors.push(SeriesError::Gap {
+                        series: series.to_string(),
+                        locale: locale.clone(),
+                        from: next,
+                        to: order - 1,
+                    });
+                }
+                if articles.len() > 1 {
+                    errors.push(SeriesError::Duplicate {
+                        series: series.to_string(),
+                        locale: locale.clone(),
+                        order,
+                        articles,
+                    });
+                }
+                next = order.saturating_add(1);
+            }
         }
+        errors
+    }
+
+    /// Finds a course by its slug in `O(1)`
+    pub fn course(&self, slug: &str) -> Option<&Course> {
+        self.course_index.get(slug).map(|i| &self.courses[*i])
+    }
+    /// The resource a lesson of a course references
+    pub fn lesson(&self, lesson: &Lesson) -> Option<&dyn Resource> {
+        self.resource(&lesson.id())
+    }
+    /// The courses teaching the resource
+    pub fn courses_with(&self, resource: &(impl Resource + ?Sized)) -> Vec<&Course> {
+        self.courses
+            .iter()
+            .filter(|x| x.position(resource).is_some())
+            .collect()
+    }
+    /// Validates every course against the resources, see [`Course::validate`]
+    pub fn validate_courses(&self) -> Vec<CourseError> {
+        self.courses.iter().flat_map(|x| x.validate(self)).collect()
+    }
+
+    /// Finds an exercise by its slug in `O(1)`
+    pub fn exercise(&self, slug: &str) -> Option<&Exercise> {
+        self.exercise_index.get(slug).map(|i| &self.exercises[*i])
+    }
+    /// Finds a glossary term by its slug in `O(1)`
+    pub fn term(&self, slug: &str) -> Option<&Term> {
+        self.term_index.get(slug).map(|i| &self.glossary[*i])
+    }
+    /// The glossary of the cacher's terms, to link them from resources and render the index page
+    pub fn glossary(&self) -> Glossary {
+        Glossary::new(self.glossary.clone())
+    }
 
-        // Generate search metadata for resource types
-        rty.insert(
-            ResourceType::Cheatsheet,
-            cacher
-                .cheatsheets
-                .iter()
-                .map(|x| x.as_search_meta())
-                .collect(),
-        );
-        rty.insert(
-            ResourceType::Article,
-            cacher.articles.iter().map(|x| x.as_search_meta()).collect(),
-        );
-        rty.insert(
-            ResourceType::Guide,
-            cacher.guides.iter().map(|x| x.as_search_meta()).collect(),
-        );
-
-        Self { langs, titles, rty }
-    }
-
-    pub fn search(&self, input: String) -> Vec<SearchMetadata> {
-        let lowercase_input = input.to_lowercase();
-        let lang_input = Language::from_str(&lowercase_input);
-        if let Some(metadata) = self.titles.get(&input) {
-            return metadata.clone();
-        } else if lang_input != Language::Other {
-            if let Some(metadata) = self.langs.get(&lang_input) {
-                return metadata.clone();
+    /// Every resource of the cacher, articles first, then cheatsheets, guides, courses, exercises and glossary terms
+    pub fn resources(&self) -> impl Iterator<Item = &dyn Resource> {
+        let articles = self.articles.iter().map(|x| x as &dyn Resource);
+        let cheatsheets = self.cheatsheets.iter().map(|x| x as &dyn Resource);
+        let guides = self.guides.iter().map(|x| x as &dyn Resource);
+        let courses = self.courses.iter().map(|x| x as &dyn Resource);
+        let exercises = self.exercises.iter().map(|x| x as &dyn Resource);
+        let glossary = self.glossary.iter().map(|x| x as &dyn Resource);
+        articles
+            .chain(cheatsheets)
+            .chain(guides)
+            .chain(courses)
+            .chain(exercises)
+            .chain(glossary)
+    }
+    /// The resources to search, recommend and put in the feeds: every resource in preview mode,
+    /// otherwise only the ones published now
+    pub fn visible_resources(&self) -> impl Iterator<Item = &dyn Resource> {
+        self.visible_resources_at(Utc::now())
+    }
+    /// The resources visible at the time: every resource in preview mode, otherwise only the
+    /// ones published as of the time
+    pub fn visible_resources_at(&self, at: DateTime<Utc>) -> impl Iterator<Item = &dyn Resource> {
+        self.resources().filter(move |x| self.is_visible(*x, at))
+    }
+    /// Whether the resource is visible at the time, see [`Cacher::visible_resources_at`]
+    fn is_visible(&self, resource: &(impl Resource + ?Sized), at: DateTime<Utc>) -> bool {
+        self.preview || resource.is_published_at(at)
+    }
+    /// The earliest time after `after` a scheduled resource is published at, `None` if no
+    /// resource is scheduled after it
+    pub fn next_publish_at(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.resources()
+            .filter(|x| !x.is_draft())
+            .filter_map(|x| x.publish_at())
+            .filter(|x| *x > after)
+            .min()
+    }
+    /// The resources of a type
+    pub fn resources_of(&self, ty: ResourceType) -> impl Iterator<Item = &dyn Resource> {
+        self.resources().filter(move |x| x.resource_type() == ty)
+    }
+    /// Finds a resource by its [`id`](Resource::id) in `O(n)`
+    pub fn resource(&self, id: &str) -> Option<&dyn Resource> {
+        self.resources().find(|x| x.id() == id)
+    }
+
+    /// The visible translations of the resource in every locale, including the resource itself
+    /// when it's visible, see [`Cacher::visible_resources`]
+    pub fn translations(&self, resource: &(impl Resource + ?Sized)) -> Vec<&dyn Resource> {
+        let (ty, key) = (resource.resource_type(), resource.translation_key());
+        self.visible_resources()
+            .filter(|x| x.resource_type() == ty && x.translation_key() == key)
+            .collect()
+    }
+    /// The translation of the resource in the locale, falling back to the parent locales and
+    /// then the default locale (see [`locale::fallbacks`]), `None` if there's no translation in any of them
+    pub fn translation(
+        &self,
+        resource: &(impl Resource + ?Sized),
+        locale: &str,
+    ) -> Option<&dyn Resource> {
+        closest(&self.translations(resource), locale)
+    }
+    /// Finds an article by its slug in the locale, using the visible translation in the closest
+    /// locale (see [`Cacher::translation`]) or the article itself if there's none and it's visible
+    pub fn article_in(&self, slug: &str, locale: &str) -> Option<&Article> {
+        let now = Utc::now();
+        let article = self.article(slug)?;
+        let key = article.translation_key();
+        let translations: Vec<&Article> = self
+            .articles
+            .iter()
+            .filter(|x| x.translation_key() == key && self.is_visible(*x, now))
+            .collect();
+        closest(&translations, locale).or(Some(article).filter(|x| self.is_visible(*x, now)))
+    }
+    /// Every visible article once in the locale, the translation in the closest locale standing
+    /// in for each group of translations, in the order the groups first appear
+    pub fn articles_in(&self, locale: &str) -> Vec<&Article> {
+        let now = Utc::now();
+        let mut keys: Vec<String> = Vec::new();
+        let mut groups: HashMap<String, Vec<&Article>> = HashMap::new();
+        for article in self.articles.iter().filter(|x| self.is_visible(*x, now)) {
+            let key = article.translation_key();
+            if !groups.contains_key(&key) {
+                keys.push(key.clone());
             }
-        } else if let Some(metadata) = self
-            .rty
-            .get(&ResourceType::from_str(&lowercase_input).unwrap())
-        {
-            return metadata.clone();
+            groups.entry(key).or_default().push(article);
         }
-
-        Vec::new()
+        keys.iter()
+            .map(|key| {
+                let group = &groups[key];
+                closest(group, locale).unwrap_or(group[0])
+            })
+            .collect()
+    }
+    /// The alternate language versions of the resource to link from it, one for every visible
+    /// translation including the resource itself, sorted by locale
+    pub fn alternates(&self, resource: &(impl Resource + ?Sized)) -> Vec<Alternate> {
+        let mut alternates: Vec<Alternate> = self
+            .translations(resource)
+            .into_iter()
+            .map(|x| Alternate {
+                locale: x.locale(),
+                link: x.link(),
+            })
+            .collect();
+        alternates.sort_by(|a, b| a.locale.cmp(&b.locale));
+        alternates
     }
-}
 
-impl Cacher {
-    pub fn new(articles: Vec<Article>, cheatsheets: Vec<Cheatsheet>, guides: Vec<Guide>) -> Self {
-        let updated_at = chrono::Utc::now().to_string();
-        Self {
-            updated_at,
-            articles,
-            cheatsheets,
-            guides,
+    /// A view of the cacher with only the resources published as of the time,
+    /// leaving out the drafts and the resources scheduled after it
+    pub fn published_as_of(&self, at: DateTime<Utc>) -> Cacher {
+        let mut cacher = self.clone();
+        cacher.articles.retain(|x| x.is_published_at(at));
+        cacher.cheatsheets.retain(|x| x.is_published_at(at));
+        cacher.guides.retain(|x| x.is_published_at(at));
+        cacher.courses.retain(|x| x.is_published_at(at));
+        cacher.exercises.retain(|x| x.is_published_at(at));
+        cacher.glossary.retain(|x| x.is_published_at(at));
+        cacher.preview = false;
+        cacher.reindex();
+        cacher
+    }
+    /// A view of the cacher with only the resources published now
+    pub fn published(&self) -> Cacher {
+        self.published_as_of(Utc::now())
+    }
+    /// The RSS feed of the visible articles in the default locale, see [`Cacher::visible_resources`]
+    pub fn articles_rss(&self) -> rss::Channel {
+        self.locale_rss(locale::DEFAULT_LOCALE)
+    }
+    /// The RSS feed of the visible articles in the locale
+    pub fn locale_rss(&self, locale: &str) -> rss::Channel {
+        if self.preview {
+            article::preview_rss(self.articles.clone(), locale)
+        } else {
+            article::locale_rss(self.articles.clone(), locale)
         }
     }
 
@@ -219,6 +644,40 @@ impl Cacher {
     pub fn cheatsheets(&self) -> Vec<Cheatsheet> {
         self.cheatsheets.clone()
     }
+    pub fn courses(&self) -> Vec<Course> {
+        self.courses.clone()
+    }
+    pub fn exercises(&self) -> Vec<Exercise> {
+        self.exercises.clone()
+    }
+}
+
+/// The resource in the closest locale to `locale` (see [`locale::fallbacks`])
+fn closest<'a, T: Resource + ?Sized>(resources: &[&'a T], locale: &str) -> Option<&'a T> {
+    locale::fallbacks(locale)
+        .iter()
+        .find_map(|locale| resources.iter().find(|x| x.locale() == *locale))
+        .copied()
+}
+
+/// The key of a cheatsheet in the cacher's index
+fn cheatsheet_key(lang: impl Display, slug: &str) -> (String, String) {
+    (
+        cheatsheet::normalize_lang(&lang.to_string()),
+        slug.to_string(),
+    )
+}
+
+/// Reads a `publish_at` time, either a date (YYYY-MM-DD) taken as midnight UTC or an RFC 3339 time
+pub(crate) fn parse_publish_at(publish_at: &str) -> Option<DateTime<Utc>> {
+    let publish_at = publish_at.trim();
+    match DateTime::parse_from_rfc3339(publish_at) {
+        Ok(time) => Some(time.with_timezone(&Utc)),
+        Err(_) => NaiveDate::parse_from_str(publish_at, "%Y-%m-%d")
+            .ok()
+            .and_then(|x| x.and_hms_opt(0, 0, 0))
+            .map(|x| Utc.from_utc_datetime(&x)),
+    }
 }
 
 impl From<String> for Cacher {
@@ -1,10 +1,14 @@
-use chrono::Utc;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use chrono::{DateTime, Utc};
 use pulldown_cmark::{html, Options, Parser};
 use rss::Item;
 use serde::{Deserialize, Serialize};
 
-use crate::resources::{ResourceType, SearchMetadata};
+use crate::resources::{parse_publish_at, Resource, ResourceType, SearchMetadata};
 use crate::resources::cheatsheet::Language;
+use crate::resources::locale::{self, DEFAULT_LOCALE};
 
 use super::Parser as ArticleParser;
 
@@ -30,6 +34,17 @@ use super::Parser as ArticleParser;
 ///
 /// Content of the article
 /// ```
+///
+/// Parts of a multi-part tutorial share a `series` and are ordered from 1 by `series_order`:
+///
+/// ```markdown
+/// ---
+/// title: Async Rust Part 2
+/// series: Async Rust
+/// series_order: 2
+/// ...
+/// ---
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Article {
     metadata: Metadata,
@@ -45,6 +60,12 @@ pub struct Article {
 /// - `date`: The date the article was published (YYYY-MM-DD).
 /// - `tags`: A list of tags for the article.
 /// - `icon`: The icon to use for the article (`devicon` or `fontawesome5`).
+/// - `series`: Optional name of the series the article is a part of.
+/// - `series_order`: The position of the article in its series, starting at 1.
+/// - `draft`: Optional, `true` keeps the article out of the published resources.
+/// - `publish_at`: Optional date (YYYY-MM-DD) or RFC 3339 time the article is published at.
+/// - `locale`: Optional human language the article is written in (e.g. `es`), defaults to `en`.
+/// - `translation_key`: Optional key shared by the translations of the article, defaults to its slug.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Metadata {
     title: String,
@@ -53,6 +74,18 @@ pub struct Metadata {
     icon: String,
     date: String,
     tags: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    series: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    series_order: Option<u32>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    draft: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    publish_at: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    locale: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    translation_key: String,
 }
 
 impl Metadata {
@@ -65,10 +98,129 @@ impl Metadata {
             icon: icon.to_string(),
             date,
             tags: tags.to_string(),
+            series: String::new(),
+            series_order: None,
+            draft: false,
+            publish_at: None,
+            locale: String::new(),
+            translation_key: String::new(),
         }
     }
+    /// Sets the publication date (YYYY-MM-DD) instead of today
+    pub fn with_date(mut self, date: &str) -> Self {
+        self.date = date.to_string();
+        self
+    }
+    /// Makes the article the part of a series at the order, starting at 1
+    pub fn with_series(mut self, series: &str, order: u32) -> Self {
+        self.series = series.to_string();
+        self.series_order = Some(order);
+        self
+    }
+    /// Marks the article as a draft, hidden until it's unmarked
+    pub fn with_draft(mut self, draft: bool) -> Self {
+        self.draft = draft;
+        self
+    }
+    /// Schedules the article to be published at the date (YYYY-MM-DD) or RFC 3339 time
+    pub fn with_publish_at(mut self, publish_at: &str) -> Self {
+        self.publish_at = Some(publish_at.to_string());
+        self
+    }
+    /// Sets the human language the article is written in
+    pub fn with_locale(mut self, locale: &str) -> Self {
+        self.locale = locale.to_string();
+        self
+    }
+    /// Sets the key shared by the translations of the article
+    pub fn with_translation_key(mut self, translation_key: &str) -> Self {
+        self.translation_key = translation_key.to_string();
+        self
+    }
+}
+
+/// A problem found while validating the order of a series of articles, the series of each
+/// locale being separate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeriesError {
+    /// An article of the series has no `series_order`
+    MissingOrder {
+        series: String,
+        locale: String,
+        article: String,
+    },
+    /// An article of the series has a `series_order` of 0, while parts start at 1
+    ZeroOrder {
+        series: String,
+        locale: String,
+        article: String,
+    },
+    /// More than one article of the series has the same order
+    Duplicate {
+        series: String,
+        locale: String,
+        order: u32,
+        articles: Vec<String>,
+    },
+    /// The parts `from` to `to` of the series, both included, are missing
+    Gap {
+        series: String,
+        locale: String,
+        from: u32,
+        to: u32,
+    },
 }
 
+impl Display for SeriesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SeriesError::MissingOrder {
+                series,
+                locale,
+                article,
+            } => write!(
+                f,
+                "article `{article}` of series `{series}` ({locale}) has no order"
+            ),
+            SeriesError::ZeroOrder {
+                series,
+                locale,
+                article,
+            } => write!(
+                f,
+                "article `{article}` of series `{series}` ({locale}) has order 0, parts start at 1"
+            ),
+            SeriesError::Duplicate {
+                series,
+                locale,
+                order,
+                articles,
+            } => write!(
+                f,
+                "series `{series}` ({locale}) has more than one part {order}: {}",
+                articles.join(", ")
+            ),
+            SeriesError::Gap {
+                series,
+                locale,
+                from,
+                to,
+            } if from == to => write!(f, "series `{series}` ({locale}) is missing part {from}"),
+            SeriesError::Gap {
+                series,
+                locale,
+                from,
+                to,
+            } => write!(
+                f,
+                "series `{series}` ({locale}) is missing parts {from} to {to}"
+            ),
+        }
+    }
+}
+
+impl Error for SeriesError {}
+
 impl ArticleParser for Article {
     fn parse_raw(markdown: &str) -> Self
         where
@@ -116,11 +268,12 @@ impl ArticleParser for Article {
 }
 
 impl Article {
-    fn link(&self) -> String {
-        format!("https://moka-reads.mkproj.com/articles/{}", self.slug)
-    }
+    /// Articles in other locales than the default are under their locale, e.g. `/es/articles/Hola`
     pub fn link_short(&self) -> String {
-        format!("/articles/{}", self.slug)
+        match self.locale() {
+            locale if locale == DEFAULT_LOCALE => format!("/articles/{}", self.slug),
+            locale => format!("/{locale}/articles/{}", self.slug),
+        }
     }
     pub fn to_rss_item(&self) -> Item {
         let mut item = Item::default();
@@ -137,24 +290,18 @@ impl Article {
         item.set_pub_date(self.metadata.date.clone());
         item
     }
-    /// A `O(n)` way to find an Article
-    pub fn find(articles: &[Article], slug: &str) -> Self {
-        Self::find_sync(articles, slug)
+    /// A `O(n)` way to find an Article, see [`Cacher::article`](crate::resources::Cacher::article)
+    /// for a constant time lookup
+    pub fn find<'a>(articles: &'a [Article], slug: &str) -> Option<&'a Self> {
+        articles.iter().find(|x| x.slug == slug)
     }
-    /// A `O(log n)` way to find an Article using binary search
-    pub fn search(articles: &[Article], slug: &str) -> Self {
-        match articles.binary_search_by(|x| x.slug.cmp(&slug.to_string())).ok() {
-            Some(index) => articles[index].clone(),
-            None => Self::default()
-        }
-    }
-
-    fn find_sync(articles: &[Article], slug: &str) -> Self {
+    /// A `O(log n)` way to find an Article using binary search,
+    /// the articles must be sorted by their slug
+    pub fn search<'a>(articles: &'a [Article], slug: &str) -> Option<&'a Self> {
         articles
-            .iter()
-            .find(|x| x.slug == slug)
-            .cloned()
-            .unwrap_or_default()
+            .binary_search_by(|x| x.slug.as_str().cmp(slug))
+            .ok()
+            .map(|index| &articles[index])
     }
 
     pub fn new(metadata: Metadata, content: String) -> Self {
@@ -176,6 +323,29 @@ impl Article {
     pub fn title(&self) -> String {
         self.metadata.title.to_string()
     }
+    pub fn author(&self) -> String {
+        self.metadata.author.to_string()
+    }
+    /// The date the article was published (YYYY-MM-DD)
+    pub fn date(&self) -> String {
+        self.metadata.date.to_string()
+    }
+    pub fn description(&self) -> String {
+        self.metadata.description.to_string()
+    }
+    /// The comma separated tags of the article
+    pub fn tags(&self) -> Vec<String> {
+        self.metadata
+            .tags
+            .split(',')
+            .map(|x| x.trim().to_string())
+            .filter(|x| !x.is_empty())
+            .collect()
+    }
+    /// The content of the article, markdown when parsed raw and html otherwise
+    pub fn content(&self) -> &str {
+        &self.content
+    }
     pub fn as_search_meta(&self) -> SearchMetadata {
         SearchMetadata::new(
             self.title(),
@@ -184,24 +354,118 @@ impl Article {
             self.metadata.tags.clone(),
         )
     }
+    /// The name of the series the article is a part of
+    pub fn series(&self) -> Option<&str> {
+        Some(self.metadata.series.as_str()).filter(|x| !x.is_empty())
+    }
+    /// The position of the article in its series, starting at 1
+    pub fn series_order(&self) -> Option<u32> {
+        self.metadata.series_order
+    }
     pub fn lang_in_tag(&self, lang: Language) -> bool {
         let lang = lang.to_string();
         self.metadata.tags.contains(&lang)
     }
 }
 
+impl Resource for Article {
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Article
+    }
+    fn title(&self) -> String {
+        Article::title(self)
+    }
+    fn slug(&self) -> &str {
+        &self.slug
+    }
+    fn author(&self) -> String {
+        Article::author(self)
+    }
+    /// The languages whose name is one of the tags of the article, ignoring case, which is how
+    /// the searcher has always matched articles to languages. Unlike [`Article::lang_in_tag`],
+    /// a language only named inside a longer tag doesn't count.
+    fn languages(&self) -> Vec<Language> {
+        self.tags()
+            .iter()
+            .map(|x| Language::from_str(&x.to_lowercase()))
+            .filter(|x| *x != Language::Other)
+            .collect()
+    }
+    fn tags(&self) -> Vec<String> {
+        Article::tags(self)
+    }
+    fn description(&self) -> String {
+        Article::description(self)
+    }
+    fn content(&self) -> &str {
+        Article::content(self)
+    }
+    fn published(&self) -> Option<String> {
+        Some(self.date())
+    }
+    /// Unreadable `publish_at` times keep the article a draft rather than publishing it early
+    fn is_draft(&self) -> bool {
+        self.metadata.draft
+            || self
+                .metadata
+                .publish_at
+                .as_deref()
+                .is_some_and(|x| parse_publish_at(x).is_none())
+    }
+    fn publish_at(&self) -> Option<DateTime<Utc>> {
+        self.metadata
+            .publish_at
+            .as_deref()
+            .and_then(parse_publish_at)
+    }
+    fn locale(&self) -> String {
+        Some(locale::normalize(&self.metadata.locale))
+            .filter(|x| !x.is_empty())
+            .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+    }
+    fn translation_key(&self) -> String {
+        Some(self.metadata.translation_key.trim().to_string())
+            .filter(|x| !x.is_empty())
+            .unwrap_or_else(|| self.slug.clone())
+    }
+    fn link_short(&self) -> String {
+        Article::link_short(self)
+    }
+    fn as_search_meta(&self) -> SearchMetadata {
+        Article::as_search_meta(self)
+    }
+}
+
+/// The RSS feed of the published articles in the default locale,
+/// drafts and articles scheduled later are left out
 pub fn articles_rss(articles: Vec<Article>) -> rss::Channel {
+    locale_rss(articles, DEFAULT_LOCALE)
+}
+
+/// The RSS feed of the published articles in the locale
+pub fn locale_rss(articles: Vec<Article>, locale: &str) -> rss::Channel {
+    let now = Utc::now();
+    rss_channel(articles.iter().filter(|x| x.is_published_at(now)), locale)
+}
+
+/// The RSS feed of every article in the locale including the drafts and scheduled ones, to preview them
+pub fn preview_rss(articles: Vec<Article>, locale: &str) -> rss::Channel {
+    rss_channel(articles.iter(), locale)
+}
+
+fn rss_channel<'a>(articles: impl Iterator<Item = &'a Article>, locale: &str) -> rss::Channel {
+    let locale = locale::normalize(locale);
     let mut channel = rss::Channel::default();
     let now = Utc::now().to_rfc2822();
     channel.set_last_build_date(now);
     channel.set_title("Moka Reads".to_string());
     channel.set_link("https://moka-reads.mkproj.com".to_string());
     channel.set_description("An Opensource Education Platform".to_string());
-    channel.set_language("en".to_string());
+    channel.set_language(locale.clone());
     channel.set_ttl("60".to_string());
     channel.set_items(
         articles
-            .iter()
+            .filter(|article| article.locale() == locale)
             .map(|article| article.to_rss_item())
             .collect::<Vec<Item>>(),
     );
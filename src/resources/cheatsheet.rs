@@ -1,12 +1,13 @@
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
-use futures::stream::FuturesUnordered;
+use chrono::{DateTime, Utc};
 use mokareads_macros::EnumVariants;
 use pulldown_cmark::{html, Options, Parser};
 use serde::{Deserialize, Serialize};
 
-use crate::resources::ResourceType;
+use crate::resources::version::Version;
+use crate::resources::{parse_publish_at, Resource, ResourceType};
 
 use super::Parser as CheatsheetParser;
 use super::SearchMetadata;
@@ -26,6 +27,11 @@ use super::SearchMetadata;
 /// level: 1
 /// language: python
 /// icon: devicon
+/// prerequisites:
+///    - Python_Basics
+/// next:
+///    - Python_Decorators
+/// language_version: "3.10"
 /// ---
 /// Content of the cheat sheet
 /// ```
@@ -46,25 +52,17 @@ impl Cheatsheet {
             content,
         }
     }
-    /// Asynchronously fetches a cheatsheet from a list of cheatsheets
-    pub async fn fetch(cheatsheets: &[Self], slug: String, lang: String) -> Self {
-        let unordered = FuturesUnordered::from_iter(cheatsheets)
-            .iter()
-            .find(|x| x.slug == slug && x.lang() == lang)
-            .cloned();
-        match unordered {
-            Some(c) => c.clone(),
-            None => Cheatsheet::default(),
-        }
+    /// Fetches a cheatsheet from a list of cheatsheets, the same as [`Cheatsheet::find`]
+    #[deprecated(note = "it never awaited, use `Cheatsheet::find` or `Cacher::cheatsheet`")]
+    pub fn fetch(cheatsheets: &[Self], slug: String, lang: String) -> Option<&Self> {
+        Self::find(cheatsheets, slug, lang)
     }
-    /// Synchronously finds a cheatsheet from a list of cheatsheets
-    /// Reason of having a dedicated function is to be able to control the method
-    pub fn find(cheatsheets: &[Self], slug: String, lang: String) -> Self {
+    /// Synchronously finds a cheatsheet from a list of cheatsheets in `O(n)`,
+    /// see [`Cacher::cheatsheet`](crate::resources::Cacher::cheatsheet) for a constant time lookup
+    pub fn find(cheatsheets: &[Self], slug: String, lang: String) -> Option<&Self> {
         cheatsheets
             .iter()
             .find(|x| x.slug == slug && x.lang() == lang)
-            .cloned()
-            .unwrap_or_default()
     }
     pub fn to_markdown(&self) -> String {
         let mut parts = Vec::new();
@@ -80,9 +78,50 @@ impl Cheatsheet {
     pub fn lang(&self) -> String {
         self.metadata.lang.clone()
     }
+    /// The language of the cheat sheet as a [`Language`], read from [`Cheatsheet::lang_key`]
+    /// so it matches the way the cacher looks cheat sheets up
+    pub fn language(&self) -> Language {
+        Language::from_str(&self.lang_key())
+    }
+    /// The language of the cheat sheet as it's indexed by, see [`normalize_lang`]
+    pub fn lang_key(&self) -> String {
+        normalize_lang(&self.metadata.lang)
+    }
+    /// The level of the cheat sheet, defaulting to [`Level::Beginner`] if it is out of range
+    pub fn level(&self) -> Level {
+        Level::from_u8(self.metadata.level).unwrap_or(Level::Beginner)
+    }
+    /// Slugs of the cheat sheets to read before this one
+    pub fn prerequisites(&self) -> &[String] {
+        &self.metadata.prerequisites
+    }
+    /// Slugs of the cheat sheets to read after this one
+    pub fn next(&self) -> &[String] {
+        &self.metadata.next
+    }
+    /// The language version the cheat sheet targets, if it states a valid one
+    pub fn language_version(&self) -> Option<Version> {
+        self.metadata.language_version.as_deref()?.parse().ok()
+    }
+    /// The language edition the cheat sheet targets, if it states a valid one
+    pub fn edition(&self) -> Option<Version> {
+        self.metadata.edition.as_deref()?.parse().ok()
+    }
+    /// Checks if the cheat sheet targets at least the `min` language version,
+    /// a cheat sheet without a version is assumed to apply to any version
+    pub fn targets_at_least(&self, min: &Version) -> bool {
+        self.language_version().is_none_or(|version| version >= *min)
+    }
     pub fn title(&self) -> String {
         self.metadata.title.to_string()
     }
+    pub fn author(&self) -> String {
+        self.metadata.author.to_string()
+    }
+    /// Content of the cheat sheet, either the raw markdown or the parsed html
+    pub fn content(&self) -> &str {
+        &self.content
+    }
     pub fn link_short(&self) -> String {
         format!("/cheatsheets/{}", &self.slug)
     }
@@ -93,6 +132,56 @@ impl Cheatsheet {
             self.link_short(),
             self.lang(),
         )
+        .with_version(self.metadata.language_version.clone())
+    }
+}
+
+impl Resource for Cheatsheet {
+    /// Cheat sheets of different languages share links, so the normalised language is part of the id
+    fn id(&self) -> String {
+        format!("{}#{}", self.link_short(), self.lang_key())
+    }
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Cheatsheet
+    }
+    fn title(&self) -> String {
+        Cheatsheet::title(self)
+    }
+    fn slug(&self) -> &str {
+        &self.slug
+    }
+    fn author(&self) -> String {
+        Cheatsheet::author(self)
+    }
+    fn languages(&self) -> Vec<Language> {
+        vec![self.language()]
+    }
+    fn content(&self) -> &str {
+        Cheatsheet::content(self)
+    }
+    fn difficulty(&self) -> Option<Level> {
+        Some(self.level())
+    }
+    /// Unreadable `publish_at` times keep the cheat sheet a draft rather than publishing it early
+    fn is_draft(&self) -> bool {
+        self.metadata.draft
+            || self
+                .metadata
+                .publish_at
+                .as_deref()
+                .is_some_and(|x| parse_publish_at(x).is_none())
+    }
+    fn publish_at(&self) -> Option<DateTime<Utc>> {
+        self.metadata
+            .publish_at
+            .as_deref()
+            .and_then(parse_publish_at)
+    }
+    fn link_short(&self) -> String {
+        Cheatsheet::link_short(self)
+    }
+    fn as_search_meta(&self) -> SearchMetadata {
+        Cheatsheet::as_search_meta(self)
     }
 }
 
@@ -108,6 +197,12 @@ impl Cheatsheet {
 ///   - 3: Advanced
 /// - `language`: The language of the cheat sheet.
 /// - `icon`: The icon to use for the cheat sheet (`devicon` or `fontawesome5`).
+/// - `prerequisites`: Optional slugs of cheat sheets in the same language to read before this one.
+/// - `next`: Optional slugs of cheat sheets in the same language to read after this one.
+/// - `language_version`: Optional version of the language the content targets (e.g. `3.12`).
+/// - `edition`: Optional edition of the language the content targets (e.g. `2021` for Rust).
+/// - `draft`: Optional, `true` keeps the cheat sheet out of the published resources.
+/// - `publish_at`: Optional date (YYYY-MM-DD) or RFC 3339 time the cheat sheet is published at.
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Default)]
 pub struct Metadata {
     title: String,
@@ -115,6 +210,18 @@ pub struct Metadata {
     level: u8,
     lang: String,
     icon: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    prerequisites: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    next: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    language_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    edition: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    draft: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    publish_at: Option<String>,
 }
 
 impl Metadata {
@@ -125,11 +232,47 @@ impl Metadata {
             level,
             lang: lang.to_string(),
             icon: icon.to_string(),
+            prerequisites: Vec::new(),
+            next: Vec::new(),
+            language_version: None,
+            edition: None,
+            draft: false,
+            publish_at: None,
         }
     }
+    /// Sets the slugs of the cheat sheets that should be read before this one
+    pub fn with_prerequisites(mut self, prerequisites: &[&str]) -> Self {
+        self.prerequisites = prerequisites.iter().map(|x| x.replace(' ', "_")).collect();
+        self
+    }
+    /// Sets the slugs of the cheat sheets that should be read after this one
+    pub fn with_next(mut self, next: &[&str]) -> Self {
+        self.next = next.iter().map(|x| x.replace(' ', "_")).collect();
+        self
+    }
+    /// Sets the language version the cheat sheet targets
+    pub fn with_language_version(mut self, version: &str) -> Self {
+        self.language_version = Some(version.to_string());
+        self
+    }
+    /// Sets the language edition the cheat sheet targets
+    pub fn with_edition(mut self, edition: &str) -> Self {
+        self.edition = Some(edition.to_string());
+        self
+    }
+    /// Marks the cheat sheet as a draft, hidden until it's unmarked
+    pub fn with_draft(mut self, draft: bool) -> Self {
+        self.draft = draft;
+        self
+    }
+    /// Schedules the cheat sheet to be published at the date (YYYY-MM-DD) or RFC 3339 time
+    pub fn with_publish_at(mut self, publish_at: &str) -> Self {
+        self.publish_at = Some(publish_at.to_string());
+        self
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq, Hash)]
 #[repr(u8)]
 pub enum Level {
     Beginner = 1,
@@ -138,7 +281,7 @@ pub enum Level {
 }
 
 impl Level {
-    fn from_u8(value: u8) -> Option<Level> {
+    pub fn from_u8(value: u8) -> Option<Level> {
         match value {
             1 => Some(Level::Beginner),
             2 => Some(Level::Intermediate),
@@ -240,6 +383,20 @@ impl Language {
             Language::Other => "devicon-github-original".to_string(),
         }
     }
+    /// The name of the language as it's written in prose, e.g. `C++`
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Language::Kotlin => "Kotlin",
+            Language::Rust => "Rust",
+            Language::C => "C",
+            Language::CPP => "C++",
+            Language::Zig => "Zig",
+            Language::Python => "Python",
+            Language::Swift => "Swift",
+            Language::Go => "Go",
+            Language::Other => "Other",
+        }
+    }
 }
 
 impl Display for Language {
@@ -259,10 +416,17 @@ impl Display for Language {
     }
 }
 
+/// Normalises the language of a cheat sheet into the key it's indexed by, lowercase like the
+/// [`Display`] of a [`Language`] so languages without a variant stay apart instead of all
+/// becoming [`Language::Other`]
+pub fn normalize_lang(lang: &str) -> String {
+    lang.trim().to_lowercase()
+}
+
 fn get_lang_vec(lang: Language, cheatsheets: &[Cheatsheet]) -> Vec<Cheatsheet> {
     cheatsheets
         .iter()
-        .filter(|x| Language::from_str(&x.metadata.lang) == lang)
+        .filter(|x| x.language() == lang)
         .cloned()
         .collect()
 }
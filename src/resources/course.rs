@@ -0,0 +1,398 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use chrono::Utc;
+use pulldown_cmark::{html, Options, Parser};
+use serde::{Deserialize, Serialize};
+
+use crate::resources::cheatsheet::{normalize_lang, Language, Level};
+use crate::resources::locale::{self, DEFAULT_LOCALE};
+use crate::resources::{Cacher, Resource, ResourceType, SearchMetadata};
+
+use super::Parser as CourseParser;
+
+/// # MoKa Reads Course
+///
+/// Courses sequence existing articles, cheat sheets and guides into lessons,
+/// like "Rust in 10 lessons". The content of a course is its introduction.
+///
+/// ## Markdown Format:
+///
+/// ```markdown
+/// ---
+/// title: Rust in 10 Lessons
+/// description: From ownership to async
+/// author: John Doe
+/// level: 1
+/// lang: rust
+/// icon: devicon
+/// lessons:
+///    - article/Ownership_Explained
+///    - article/es/Propiedad_Explicada
+///    - cheatsheet/rust/Traits
+///    - guide/Rust_Book
+/// ---
+/// Introduction of the course
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(from = "CourseData")]
+pub struct Course {
+    metadata: Metadata,
+    pub slug: String,
+    content: String,
+    /// The lessons parsed from the metadata, rebuilt whenever a course is deserialized
+    #[serde(skip)]
+    lessons: Vec<Lesson>,
+}
+
+/// The serialized fields of a [`Course`]
+#[derive(Deserialize)]
+struct CourseData {
+    metadata: Metadata,
+    slug: String,
+    content: String,
+}
+
+impl From<CourseData> for Course {
+    fn from(value: CourseData) -> Self {
+        let lessons = parse_lessons(&value.metadata);
+        Self {
+            metadata: value.metadata,
+            slug: value.slug,
+            content: value.content,
+            lessons,
+        }
+    }
+}
+
+/// # Course Metadata
+///
+/// The metadata section is a YAML document which contains the following fields:
+///
+/// - `title`: The title of the course.
+/// - `description`: A short description of the course.
+/// - `author`: The author of the course.
+/// - `level`: The level of the course (1, 2, or 3), like cheat sheets.
+/// - `lang`: Optional language the course teaches.
+/// - `icon`: The icon to use for the course (`devicon` or `fontawesome5`).
+/// - `lessons`: The resources of the course in order, see [`Lesson`].
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Default)]
+pub struct Metadata {
+    title: String,
+    description: String,
+    author: String,
+    level: u8,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    lang: String,
+    icon: String,
+    #[serde(default)]
+    lessons: Vec<String>,
+}
+
+impl Metadata {
+    pub fn new(title: &str, description: &str, author: &str, level: u8, icon: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            description: description.to_string(),
+            author: author.to_string(),
+            level,
+            lang: String::new(),
+            icon: icon.to_string(),
+            lessons: Vec::new(),
+        }
+    }
+    /// Sets the language the course teaches
+    pub fn with_lang(mut self, lang: &str) -> Self {
+        self.lang = lang.to_string();
+        self
+    }
+    /// Sets the lessons of the course in order, see [`Lesson`] for how they're written
+    pub fn with_lessons(mut self, lessons: &[&str]) -> Self {
+        self.lessons = lessons.iter().map(|x| x.to_string()).collect();
+        self
+    }
+}
+
+/// A resource taught in a course, written `article/<slug>`, `article/<locale>/<slug>`,
+/// `cheatsheet/<lang>/<slug>` or `guide/<repo name>` in the frontmatter. Spaces in slugs are
+/// read as underscores, an article without a locale is in [`DEFAULT_LOCALE`], and the locale of
+/// an article and the language of a cheat sheet are normalised like the cacher's (see
+/// [`locale::normalize`] and [`normalize_lang`]), so `cheatsheet/Rust/Traits` is
+/// `cheatsheet/rust/Traits`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Lesson {
+    Article { locale: String, slug: String },
+    Cheatsheet { lang: String, slug: String },
+    Guide(String),
+}
+
+impl Lesson {
+    /// The [`id`](Resource::id) of the resource the lesson references
+    pub fn id(&self) -> String {
+        match self {
+            Lesson::Article { locale, slug } if locale == DEFAULT_LOCALE => {
+                format!("/articles/{slug}")
+            }
+            Lesson::Article { locale, slug } => format!("/{locale}/articles/{slug}"),
+            Lesson::Cheatsheet { lang, slug } => format!("/cheatsheets/{slug}#{lang}"),
+            Lesson::Guide(repo_name) => format!("/guides/{repo_name}"),
+        }
+    }
+}
+
+impl FromStr for Lesson {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let slug = |x: &str| Some(x.trim().replace(' ', "_")).filter(|x| !x.is_empty());
+        let (ty, rest) = s.trim().split_once('/').ok_or(())?;
+        match ty {
+            "article" => {
+                let (locale, rest) = match rest.split_once('/') {
+                    Some((locale, rest)) => (locale::normalize(locale), rest),
+                    None => (DEFAULT_LOCALE.to_string(), rest),
+                };
+                if locale.is_empty() {
+                    return Err(());
+                }
+                Ok(Lesson::Article {
+                    locale,
+                    slug: slug(rest).ok_or(())?,
+                })
+            }
+            "cheatsheet" => {
+                let (lang, rest) = rest.split_once('/').ok_or(())?;
+                Ok(Lesson::Cheatsheet {
+                    lang: normalize_lang(&slug(lang).ok_or(())?),
+                    slug: slug(rest).ok_or(())?,
+                })
+            }
+            "guide" => Ok(Lesson::Guide(slug(rest).ok_or(())?)),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for Lesson {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Lesson::Article { locale, slug } if locale == DEFAULT_LOCALE => {
+                write!(f, "article/{slug}")
+            }
+            Lesson::Article { locale, slug } => write!(f, "article/{locale}/{slug}"),
+            Lesson::Cheatsheet { lang, slug } => write!(f, "cheatsheet/{lang}/{slug}"),
+            Lesson::Guide(repo_name) => write!(f, "guide/{repo_name}"),
+        }
+    }
+}
+
+/// A problem found while validating a course against the resources of a [`Cacher`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CourseError {
+    /// The course has no lessons
+    Empty { course: String },
+    /// A lesson isn't written as a [`Lesson`]
+    Invalid { course: String, lesson: String },
+    /// A lesson references a resource that doesn't exist
+    Unknown { course: String, lesson: String },
+    /// A lesson references a draft or a resource scheduled for later
+    Unpublished { course: String, lesson: String },
+    /// A lesson appears more than once
+    Duplicate { course: String, lesson: String },
+}
+
+impl Display for CourseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CourseError::Empty { course } => write!(f, "course `{course}` has no lessons"),
+            CourseError::Invalid { course, lesson } => {
+                write!(f, "course `{course}` has an invalid lesson `{lesson}`")
+            }
+            CourseError::Unknown { course, lesson } => {
+                write!(
+                    f,
+                    "course `{course}` references unknown resource `{lesson}`"
+                )
+            }
+            CourseError::Unpublished { course, lesson } => {
+                write!(
+                    f,
+                    "course `{course}` references unpublished resource `{lesson}`"
+                )
+            }
+            CourseError::Duplicate { course, lesson } => {
+                write!(f, "course `{course}` repeats lesson `{lesson}`")
+            }
+        }
+    }
+}
+
+impl Error for CourseError {}
+
+impl Course {
+    pub fn new(metadata: Metadata, content: String) -> Self {
+        let slug = metadata.title.replace(' ', "_");
+        let lessons = parse_lessons(&metadata);
+        Self {
+            metadata,
+            slug,
+            content,
+            lessons,
+        }
+    }
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        markdown.push_str("---\n");
+        markdown.push_str(&serde_yaml::to_string(&self.metadata).unwrap());
+        markdown.push_str("---\n");
+        markdown.push_str(&self.content);
+        markdown
+    }
+    /// The level of the course, defaulting to [`Level::Beginner`] if it is out of range
+    pub fn level(&self) -> Level {
+        Level::from_u8(self.metadata.level).unwrap_or(Level::Beginner)
+    }
+    /// The lessons of the course in order, skipping the ones that aren't valid
+    pub fn lessons(&self) -> &[Lesson] {
+        &self.lessons
+    }
+    /// The position of the resource in the lessons, starting at 0
+    pub fn position(&self, resource: &(impl Resource + ?Sized)) -> Option<usize> {
+        let id = resource.id();
+        self.lessons.iter().position(|x| x.id() == id)
+    }
+    /// The lesson before the resource, `None` for the first lesson or a resource outside the course
+    pub fn previous(&self, resource: &(impl Resource + ?Sized)) -> Option<&Lesson> {
+        let position = self.position(resource)?;
+        self.lessons.get(position.checked_sub(1)?)
+    }
+    /// The lesson after the resource, `None` for the last lesson or a resource outside the course
+    pub fn next(&self, resource: &(impl Resource + ?Sized)) -> Option<&Lesson> {
+        let position = self.position(resource)?;
+        self.lessons.get(position + 1)
+    }
+    /// Returns every invalid, unknown, unpublished and repeated lesson of the course.
+    ///
+    /// A lesson is unpublished when it references a draft or a resource scheduled for later,
+    /// unless the cacher is in preview mode (see [`Cacher::with_preview`]).
+    pub fn validate(&self, cacher: &Cacher) -> Vec<CourseError> {
+        let now = Utc::now();
+        let course = self.slug.clone();
+        if self.metadata.lessons.is_empty() {
+            return vec![CourseError::Empty { course }];
+        }
+        let mut errors = Vec::new();
+        let mut seen = HashSet::new();
+        for raw in &self.metadata.lessons {
+            let lesson = raw.to_string();
+            let course = course.clone();
+            match raw.parse::<Lesson>() {
+                Err(()) => errors.push(CourseError::Invalid { course, lesson }),
+                Ok(x) if !seen.insert(x.id()) => {
+                    errors.push(CourseError::Duplicate { course, lesson })
+                }
+                Ok(x) => match cacher.lesson(&x) {
+                    None => errors.push(CourseError::Unknown { course, lesson }),
+                    Some(resource) if !cacher.is_visible(resource, now) => {
+                        errors.push(CourseError::Unpublished { course, lesson })
+                    }
+                    Some(_) => {}
+                },
+            }
+        }
+        errors
+    }
+}
+
+impl Resource for Course {
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Course
+    }
+    fn title(&self) -> String {
+        self.metadata.title.to_string()
+    }
+    fn slug(&self) -> &str {
+        &self.slug
+    }
+    fn author(&self) -> String {
+        self.metadata.author.to_string()
+    }
+    fn languages(&self) -> Vec<Language> {
+        Some(Language::from_str(&self.metadata.lang))
+            .filter(|x| *x != Language::Other)
+            .into_iter()
+            .collect()
+    }
+    fn description(&self) -> String {
+        self.metadata.description.to_string()
+    }
+    /// The introduction of the course, either the raw markdown or the parsed html
+    fn content(&self) -> &str {
+        &self.content
+    }
+    fn difficulty(&self) -> Option<Level> {
+        Some(self.level())
+    }
+    fn link_short(&self) -> String {
+        format!("/courses/{}", self.slug)
+    }
+    fn as_search_meta(&self) -> SearchMetadata {
+        SearchMetadata::new(
+            self.title(),
+            ResourceType::Course,
+            self.link_short(),
+            self.metadata.lang.clone(),
+        )
+    }
+}
+
+impl CourseParser for Course {
+    fn parse_raw(markdown: &str) -> Self
+    where
+        Self: Sized,
+    {
+        let separator = "---";
+        let mut sections = markdown.splitn(3, separator);
+        sections.next();
+        let yaml_section = sections.next().unwrap_or("");
+        let content_section = sections.next().unwrap_or("");
+
+        let mut metadata = serde_yaml::from_str::<Metadata>(yaml_section).unwrap();
+        if Level::from_u8(metadata.level).is_none() {
+            metadata.level = 1;
+        }
+        Self::new(metadata, content_section.to_string())
+    }
+
+    fn parse(markdown: &str) -> Self
+    where
+        Self: Sized,
+    {
+        Self::parse_raw(markdown).raw_to_parsed()
+    }
+
+    fn raw_to_parsed(&self) -> Self
+    where
+        Self: Sized,
+    {
+        let parser = Parser::new_ext(&self.content, Options::all());
+        let mut html_output = String::new();
+        html::push_html(&mut html_output, parser);
+        Self {
+            metadata: self.metadata.clone(),
+            slug: self.slug.to_string(),
+            content: html_output,
+            lessons: self.lessons.clone(),
+        }
+    }
+}
+
+/// Parses the lessons of the metadata, skipping the ones that aren't valid
+fn parse_lessons(metadata: &Metadata) -> Vec<Lesson> {
+    metadata
+        .lessons
+        .iter()
+        .filter_map(|x| x.parse().ok())
+        .collect()
+}
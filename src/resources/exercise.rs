@@ -0,0 +1,251 @@
+use pulldown_cmark::{html, Options, Parser};
+use serde::{Deserialize, Serialize};
+
+use crate::resources::cheatsheet::{Language, Level};
+use crate::resources::{Resource, ResourceType, SearchMetadata};
+
+use super::Parser as ExerciseParser;
+
+/// # MoKa Reads Exercise
+///
+/// Exercises give learners practice after reading a cheat sheet, either as code to write
+/// or as a multiple choice quiz. The content of an exercise is its prompt, and submissions
+/// are graded with the [`Grader`](crate::grader::Grader).
+///
+/// ## Markdown Format:
+///
+/// A code exercise has starter code and hidden tests, which are appended to the submission
+/// and must exit successfully for it to pass:
+///
+/// ```markdown
+/// ---
+/// title: Double It
+/// author: John Doe
+/// level: 1
+/// lang: rust
+/// icon: devicon
+/// starter: |
+///   fn double(x: i32) -> i32 {
+///       todo!()
+///   }
+/// tests: |
+///   fn main() {
+///       assert_eq!(double(2), 4);
+///   }
+/// ---
+/// Write a function that doubles its argument.
+/// ```
+///
+/// A quiz has choices and the indices (from 0) of the right ones instead:
+///
+/// ```markdown
+/// ---
+/// title: Shared Ownership
+/// author: John Doe
+/// level: 2
+/// lang: rust
+/// icon: devicon
+/// choices:
+///   - Box
+///   - Rc
+///   - Arc
+/// answers: [1, 2]
+/// ---
+/// Which smart pointers share ownership of their value?
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct Exercise {
+    metadata: Metadata,
+    pub slug: String,
+    content: String,
+}
+
+/// # Exercise Metadata
+///
+/// The metadata section is a YAML document which contains the following fields:
+///
+/// - `title`: The title of the exercise.
+/// - `author`: The author of the exercise.
+/// - `level`: The level of the exercise (1, 2, or 3), like cheat sheets.
+/// - `lang`: The language of the exercise.
+/// - `icon`: The icon to use for the exercise (`devicon` or `fontawesome5`).
+/// - `starter`: Optional code the learner starts from.
+/// - `tests`: Hidden code appended to a submission that exits unsuccessfully if it's wrong.
+/// - `choices`: The choices of a quiz.
+/// - `answers`: The indices of the right choices of a quiz.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Default)]
+pub struct Metadata {
+    title: String,
+    author: String,
+    level: u8,
+    lang: String,
+    icon: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    starter: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    tests: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    choices: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    answers: Vec<usize>,
+}
+
+impl Metadata {
+    pub fn new(title: &str, author: &str, level: u8, lang: &str, icon: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            author: author.to_string(),
+            level,
+            lang: lang.to_string(),
+            icon: icon.to_string(),
+            starter: String::new(),
+            tests: String::new(),
+            choices: Vec::new(),
+            answers: Vec::new(),
+        }
+    }
+    /// Makes it a code exercise with the starter code and the hidden tests
+    pub fn with_code(mut self, starter: &str, tests: &str) -> Self {
+        self.starter = starter.to_string();
+        self.tests = tests.to_string();
+        self
+    }
+    /// Makes it a quiz with the choices and the indices of the right ones
+    pub fn with_choices(mut self, choices: &[&str], answers: &[usize]) -> Self {
+        self.choices = choices.iter().map(|x| x.to_string()).collect();
+        self.answers = answers.to_vec();
+        self
+    }
+}
+
+impl Exercise {
+    pub fn new(metadata: Metadata, content: String) -> Self {
+        let slug = metadata.title.replace(' ', "_");
+        Self {
+            metadata,
+            slug,
+            content,
+        }
+    }
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        markdown.push_str("---\n");
+        markdown.push_str(&serde_yaml::to_string(&self.metadata).unwrap());
+        markdown.push_str("---\n");
+        markdown.push_str(&self.content);
+        markdown
+    }
+    pub fn lang(&self) -> String {
+        self.metadata.lang.clone()
+    }
+    /// The language of the exercise as a [`Language`]
+    pub fn language(&self) -> Language {
+        Language::from_str(&self.metadata.lang)
+    }
+    /// The level of the exercise, defaulting to [`Level::Beginner`] if it is out of range
+    pub fn level(&self) -> Level {
+        Level::from_u8(self.metadata.level).unwrap_or(Level::Beginner)
+    }
+    /// Whether the exercise is a multiple choice quiz rather than code to write
+    pub fn is_quiz(&self) -> bool {
+        !self.metadata.choices.is_empty()
+    }
+    pub fn starter(&self) -> &str {
+        &self.metadata.starter
+    }
+    pub fn tests(&self) -> &str {
+        &self.metadata.tests
+    }
+    pub fn choices(&self) -> &[String] {
+        &self.metadata.choices
+    }
+    pub fn answers(&self) -> &[usize] {
+        &self.metadata.answers
+    }
+    /// The exercise without its tests and answers, to send to learners
+    pub fn without_solutions(&self) -> Self {
+        let mut exercise = self.clone();
+        exercise.metadata.tests.clear();
+        exercise.metadata.answers.clear();
+        exercise
+    }
+}
+
+impl Resource for Exercise {
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Exercise
+    }
+    fn title(&self) -> String {
+        self.metadata.title.to_string()
+    }
+    fn slug(&self) -> &str {
+        &self.slug
+    }
+    fn author(&self) -> String {
+        self.metadata.author.to_string()
+    }
+    fn languages(&self) -> Vec<Language> {
+        Some(self.language())
+            .filter(|x| *x != Language::Other)
+            .into_iter()
+            .collect()
+    }
+    /// The prompt of the exercise, either the raw markdown or the parsed html
+    fn content(&self) -> &str {
+        &self.content
+    }
+    fn difficulty(&self) -> Option<Level> {
+        Some(self.level())
+    }
+    fn link_short(&self) -> String {
+        format!("/exercises/{}", self.slug)
+    }
+    fn as_search_meta(&self) -> SearchMetadata {
+        SearchMetadata::new(
+            self.title(),
+            ResourceType::Exercise,
+            self.link_short(),
+            self.lang(),
+        )
+    }
+}
+
+impl ExerciseParser for Exercise {
+    fn parse_raw(markdown: &str) -> Self
+    where
+        Self: Sized,
+    {
+        let separator = "---";
+        let mut sections = markdown.splitn(3, separator);
+        sections.next();
+        let yaml_section = sections.next().unwrap_or("");
+        let content_section = sections.next().unwrap_or("");
+
+        let mut metadata = serde_yaml::from_str::<Metadata>(yaml_section).unwrap();
+        if Level::from_u8(metadata.level).is_none() {
+            metadata.level = 1;
+        }
+        Self::new(metadata, content_section.to_string())
+    }
+
+    fn parse(markdown: &str) -> Self
+    where
+        Self: Sized,
+    {
+        Self::parse_raw(markdown).raw_to_parsed()
+    }
+
+    fn raw_to_parsed(&self) -> Self
+    where
+        Self: Sized,
+    {
+        let parser = Parser::new_ext(&self.content, Options::all());
+        let mut html_output = String::new();
+        html::push_html(&mut html_output, parser);
+        Self {
+            metadata: self.metadata.clone(),
+            slug: self.slug.to_string(),
+            content: html_output,
+        }
+    }
+}
@@ -0,0 +1,379 @@
+use std::collections::{BTreeMap, HashSet};
+
+use pulldown_cmark::{html, Options, Parser};
+use serde::{Deserialize, Serialize};
+
+use crate::resources::cheatsheet::Language;
+use crate::resources::{Resource, ResourceType, SearchMetadata};
+
+use super::Parser as TermParser;
+
+/// Tags whose text is never linked to the glossary
+const SKIPPED_TAGS: [&str; 10] = [
+    "a", "code", "pre", "h1", "h2", "h3", "h4", "h5", "h6", "script",
+];
+
+/// # MoKa Reads Glossary Term
+///
+/// A term defined once and linked from every article and cheat sheet that uses it,
+/// like "lifetime", "monad" or "RAII". The content of a term is its definition.
+///
+/// ## Markdown Format:
+///
+/// ```markdown
+/// ---
+/// term: Lifetime
+/// aliases:
+///    - lifetimes
+/// lang: rust
+/// ---
+/// The region of code a reference is valid for.
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct Term {
+    metadata: Metadata,
+    pub slug: String,
+    content: String,
+}
+
+/// # Term Metadata
+///
+/// The metadata section is a YAML document which contains the following fields:
+///
+/// - `term`: The term being defined.
+/// - `aliases`: Optional other spellings of the term, like its plural.
+/// - `lang`: Optional language the term belongs to, it's then only linked from resources in that language.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Default)]
+pub struct Metadata {
+    term: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    aliases: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    lang: String,
+}
+
+impl Metadata {
+    pub fn new(term: &str) -> Self {
+        Self {
+            term: term.to_string(),
+            aliases: Vec::new(),
+            lang: String::new(),
+        }
+    }
+    /// Sets the other spellings of the term
+    pub fn with_aliases(mut self, aliases: &[&str]) -> Self {
+        self.aliases = aliases.iter().map(|x| x.to_string()).collect();
+        self
+    }
+    /// Sets the language the term belongs to
+    pub fn with_lang(mut self, lang: &str) -> Self {
+        self.lang = lang.to_string();
+        self
+    }
+}
+
+impl Term {
+    pub fn new(metadata: Metadata, content: String) -> Self {
+        let slug = metadata.term.replace(' ', "_");
+        Self {
+            metadata,
+            slug,
+            content,
+        }
+    }
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        markdown.push_str("---\n");
+        markdown.push_str(&serde_yaml::to_string(&self.metadata).unwrap());
+        markdown.push_str("---\n");
+        markdown.push_str(&self.content);
+        markdown
+    }
+    pub fn term(&self) -> &str {
+        &self.metadata.term
+    }
+    pub fn aliases(&self) -> &[String] {
+        &self.metadata.aliases
+    }
+    /// Whether the term is linked from a resource in the languages,
+    /// terms without a language are linked from every resource
+    pub fn applies_to(&self, languages: &[Language]) -> bool {
+        self.metadata.lang.is_empty()
+            || languages.contains(&Language::from_str(&self.metadata.lang))
+    }
+}
+
+impl Resource for Term {
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Glossary
+    }
+    fn title(&self) -> String {
+        self.metadata.term.to_string()
+    }
+    fn slug(&self) -> &str {
+        &self.slug
+    }
+    fn languages(&self) -> Vec<Language> {
+        Some(Language::from_str(&self.metadata.lang))
+            .filter(|x| *x != Language::Other)
+            .into_iter()
+            .collect()
+    }
+    /// The definition of the term, either the raw markdown or the parsed html
+    fn content(&self) -> &str {
+        &self.content
+    }
+    fn link_short(&self) -> String {
+        format!("/glossary/{}", self.slug)
+    }
+    fn as_search_meta(&self) -> SearchMetadata {
+        SearchMetadata::new(
+            self.title(),
+            ResourceType::Glossary,
+            self.link_short(),
+            self.metadata.lang.clone(),
+        )
+    }
+}
+
+impl TermParser for Term {
+    fn parse_raw(markdown: &str) -> Self
+    where
+        Self: Sized,
+    {
+        let separator = "---";
+        let mut sections = markdown.splitn(3, separator);
+        sections.next();
+        let yaml_section = sections.next().unwrap_or("");
+        let content_section = sections.next().unwrap_or("");
+
+        let metadata = serde_yaml::from_str::<Metadata>(yaml_section).unwrap();
+        Self::new(metadata, content_section.to_string())
+    }
+
+    fn parse(markdown: &str) -> Self
+    where
+        Self: Sized,
+    {
+        Self::parse_raw(markdown).raw_to_parsed()
+    }
+
+    fn raw_to_parsed(&self) -> Self
+    where
+        Self: Sized,
+    {
+        let parser = Parser::new_ext(&self.content, Options::all());
+        let mut html_output = String::new();
+        html::push_html(&mut html_output, parser);
+        Self {
+            metadata: self.metadata.clone(),
+            slug: self.slug.to_string(),
+            content: html_output,
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// # Glossary
+///
+/// The glossary terms of a [`Cacher`](crate::resources::Cacher), used to link terms
+/// from the html of articles and cheat sheets and to render the glossary index page.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Glossary {
+    terms: Vec<Term>,
+}
+
+impl Glossary {
+    pub fn new(terms: Vec<Term>) -> Self {
+        Self { terms }
+    }
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+
+    /// Links the first occurrence of every term of the glossary in the parsed html of a resource,
+    /// only using the terms that apply to its languages and never linking a term to itself
+    pub fn link(&self, resource: &(impl Resource + ?Sized)) -> String {
+        let id = resource.id();
+        let terms: Vec<&Term> = self
+            .terms
+            .iter()
+            .filter(|x| x.id() != id && x.applies_to(&resource.languages()))
+            .collect();
+        link_terms(resource.content(), &terms)
+    }
+    /// Links the first occurrence of every term that applies to the languages in the html.
+    ///
+    /// Terms and aliases are matched as whole words ignoring ASCII case, and the text of
+    /// links, code and headings is left as is. When terms overlap the longest one is linked.
+    pub fn link_html(&self, html: &str, languages: &[Language]) -> String {
+        let terms: Vec<&Term> = self
+            .terms
+            .iter()
+            .filter(|x| x.applies_to(languages))
+            .collect();
+        link_terms(html, &terms)
+    }
+
+    /// The html of the glossary index page, the terms sorted alphabetically under a heading for
+    /// their first letter, with each definition anchored by the term's slug.
+    ///
+    /// The terms must have been parsed for their definitions to be html.
+    pub fn index_html(&self) -> String {
+        let mut letters: BTreeMap<String, Vec<&Term>> = BTreeMap::new();
+        for term in &self.terms {
+            let letter = term
+                .term()
+                .chars()
+                .next()
+                .filter(|c| c.is_alphabetic())
+                .map(|c| c.to_uppercase().to_string())
+                .unwrap_or_else(|| "#".to_string());
+            letters.entry(letter).or_default().push(term);
+        }
+
+        let mut html = String::from("<nav class=\"glossary-letters\">");
+        for letter in letters.keys() {
+            html.push_str(&format!(
+                "<a href=\"#glossary-{}\">{}</a>",
+                letter_id(letter),
+                escape_html(letter)
+            ));
+        }
+        html.push_str("</nav>\n");
+        for (letter, mut terms) in letters {
+            terms.sort_by_key(|x| x.term().to_lowercase());
+            html.push_str(&format!(
+                "<h2 id=\"glossary-{}\">{}</h2>\n<dl>\n",
+                letter_id(&letter),
+                escape_html(&letter)
+            ));
+            for term in terms {
+                html.push_str(&format!(
+                    "<dt id=\"{}\"><a href=\"{}\">{}</a></dt>\n<dd>{}</dd>\n",
+                    escape_html(&term.slug),
+                    escape_html(&term.link_short()),
+                    escape_html(term.term()),
+                    term.content().trim()
+                ));
+            }
+            html.push_str("</dl>\n");
+        }
+        html
+    }
+}
+
+/// The anchor of a letter heading, `other` for terms not starting with a letter
+fn letter_id(letter: &str) -> String {
+    if letter == "#" {
+        "other".to_string()
+    } else {
+        letter.to_lowercase()
+    }
+}
+
+/// A spelling of a term to look for in html text
+struct Pattern<'a> {
+    text: String,
+    term: &'a Term,
+}
+
+fn link_terms(html: &str, terms: &[&Term]) -> String {
+    let mut patterns: Vec<Pattern> = terms
+        .iter()
+        .flat_map(|term| {
+            Some(term.term())
+                .into_iter()
+                .chain(term.aliases().iter().map(|x| x.as_str()))
+                .filter(|x| !x.trim().is_empty())
+                .map(|x| Pattern {
+                    text: escape_html(x.trim()),
+                    term,
+                })
+        })
+        .collect();
+    patterns.sort_by_key(|x| std::cmp::Reverse(x.text.len()));
+
+    let mut linked = HashSet::new();
+    let mut skipping = 0usize;
+    let mut output = String::with_capacity(html.len());
+    let mut rest = html;
+    while !rest.is_empty() {
+        match rest.find('<') {
+            Some(0) => {
+                let end = rest.find('>').map(|i| i + 1).unwrap_or(rest.len());
+                let tag = &rest[..end];
+                let name: String = tag
+                    .trim_start_matches(['<', '/'])
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphanumeric())
+                    .collect::<String>()
+                    .to_lowercase();
+                if SKIPPED_TAGS.contains(&name.as_str()) {
+                    if tag.starts_with("</") {
+                        skipping = skipping.saturating_sub(1);
+                    } else if !tag.ends_with("/>") {
+                        skipping += 1;
+                    }
+                }
+                output.push_str(tag);
+                rest = &rest[end..];
+            }
+            next => {
+                let end = next.unwrap_or(rest.len());
+                let text = &rest[..end];
+                if skipping == 0 {
+                    output.push_str(&link_text(text, &patterns, &mut linked));
+                } else {
+                    output.push_str(text);
+                }
+                rest = &rest[end..];
+            }
+        }
+    }
+    output
+}
+
+/// Links the first occurrence of each term not linked yet in a text node
+fn link_text<'a>(text: &str, patterns: &[Pattern<'a>], linked: &mut HashSet<&'a str>) -> String {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+    let mut i = 0;
+    while i < text.len() {
+        let starts_word = !text[..i].chars().next_back().is_some_and(is_word);
+        let found = patterns.iter().find(|p| {
+            let end = i + p.text.len();
+            starts_word
+                && !linked.contains(p.term.slug.as_str())
+                && text.is_char_boundary(end)
+                && end <= text.len()
+                && text[i..end].eq_ignore_ascii_case(&p.text)
+                && !text[end..].chars().next().is_some_and(is_word)
+        });
+        match found {
+            Some(pattern) => {
+                let end = i + pattern.text.len();
+                linked.insert(pattern.term.slug.as_str());
+                output.push_str(&text[last..i]);
+                output.push_str(&format!(
+                    "<a class=\"glossary-term\" href=\"{}\" title=\"{}\">{}</a>",
+                    escape_html(&pattern.term.link_short()),
+                    escape_html(pattern.term.term()),
+                    &text[i..end]
+                ));
+                last = end;
+                i = end;
+            }
+            None => i += text[i..].chars().next().map_or(1, |c| c.len_utf8()),
+        }
+    }
+    output.push_str(&text[last..]);
+    output
+}
@@ -1,7 +1,7 @@
 use rocket::response::Redirect;
 use serde::{Deserialize, Serialize};
 
-use crate::resources::{ResourceType, SearchMetadata};
+use crate::resources::{Resource, ResourceType, SearchMetadata};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Guide {
@@ -37,3 +37,25 @@ impl Guide {
         )
     }
 }
+
+impl Resource for Guide {
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Guide
+    }
+    fn title(&self) -> String {
+        self.unslug.clone()
+    }
+    fn slug(&self) -> &str {
+        &self.repo_name
+    }
+    /// Guides are hosted on their own site
+    fn link(&self) -> String {
+        self.addy.clone()
+    }
+    fn link_short(&self) -> String {
+        Guide::link_short(self)
+    }
+    fn as_search_meta(&self) -> SearchMetadata {
+        Guide::as_search_meta(self)
+    }
+}
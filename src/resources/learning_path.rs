@@ -0,0 +1,223 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::resources::cheatsheet::{Cheatsheet, Language, Level};
+use crate::resources::Cacher;
+
+/// A problem found while building the learning graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    /// A cheat sheet references a slug that doesn't exist in its language
+    Dangling {
+        lang: Language,
+        from: String,
+        to: String,
+    },
+    /// The slugs (in order) form a cycle, the first slug is repeated at the end
+    Cycle { lang: Language, slugs: Vec<String> },
+}
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::Dangling { lang, from, to } => {
+                write!(
+                    f,
+                    "{lang} cheatsheet `{from}` references unknown cheatsheet `{to}`"
+                )
+            }
+            GraphError::Cycle { lang, slugs } => {
+                write!(f, "{lang} cheatsheets form a cycle: {}", slugs.join(" -> "))
+            }
+        }
+    }
+}
+
+impl Error for GraphError {}
+
+/// # Learning Graph
+///
+/// A dependency graph over the cheat sheets of a [`Cacher`], built from the
+/// `prerequisites` and `next` references in their metadata.
+///
+/// An edge `a -> b` means that `a` should be read before `b`, so a prerequisite `p`
+/// of `c` gives `p -> c` and a next reference `n` of `c` gives `c -> n`.
+/// References are resolved to cheat sheets of the same language, compared by
+/// [`Cheatsheet::lang_key`] so languages without a variant are kept apart.
+#[derive(Debug, Clone)]
+pub struct LearningGraph<'a> {
+    nodes: Vec<&'a Cheatsheet>,
+    edges: Vec<Vec<usize>>,
+    dangling: Vec<GraphError>,
+}
+
+impl<'a> LearningGraph<'a> {
+    pub fn new(cheatsheets: &'a [Cheatsheet]) -> Self {
+        let nodes: Vec<&Cheatsheet> = cheatsheets.iter().collect();
+        // keyed by the language as written, so languages without a variant aren't mixed up
+        let index: HashMap<(String, &str), usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, x)| ((x.lang_key(), x.slug.as_str()), i))
+            .collect();
+
+        let mut edges = vec![Vec::new(); nodes.len()];
+        let mut dangling = Vec::new();
+        for (i, cheatsheet) in nodes.iter().enumerate() {
+            let key = cheatsheet.lang_key();
+            let mut resolve = |slug: &String| match index.get(&(key.clone(), slug.as_str())) {
+                Some(j) => Some(*j),
+                None => {
+                    dangling.push(GraphError::Dangling {
+                        lang: cheatsheet.language(),
+                        from: cheatsheet.slug.clone(),
+                        to: slug.clone(),
+                    });
+                    None
+                }
+            };
+            let prerequisites: Vec<usize> = cheatsheet
+                .prerequisites()
+                .iter()
+                .filter_map(&mut resolve)
+                .collect();
+            let next: Vec<usize> = cheatsheet.next().iter().filter_map(&mut resolve).collect();
+            for p in prerequisites {
+                edges[p].push(i);
+            }
+            edges[i].extend(next);
+        }
+        for targets in &mut edges {
+            targets.sort_unstable();
+            targets.dedup();
+        }
+
+        Self {
+            nodes,
+            edges,
+            dangling,
+        }
+    }
+
+    /// Returns every dangling reference and cycle found in the graph
+    pub fn validate(&self) -> Vec<GraphError> {
+        let mut errors = self.dangling.clone();
+        errors.extend(self.cycles());
+        errors
+    }
+
+    /// Finds the cycles in the graph using a depth first search,
+    /// reporting one cycle per back edge
+    fn cycles(&self) -> Vec<GraphError> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            Unvisited,
+            InProgress,
+            Done,
+        }
+
+        let mut marks = vec![Mark::Unvisited; self.nodes.len()];
+        let mut cycles = Vec::new();
+        for start in 0..self.nodes.len() {
+            if marks[start] != Mark::Unvisited {
+                continue;
+            }
+            // stack of (node, index of the next edge to visit)
+            let mut stack = vec![(start, 0)];
+            marks[start] = Mark::InProgress;
+            while let Some((node, edge)) = stack.last_mut() {
+                let node = *node;
+                match self.edges[node].get(*edge) {
+                    Some(&target) => {
+                        *edge += 1;
+                        match marks[target] {
+                            Mark::Unvisited => {
+                                marks[target] = Mark::InProgress;
+                                stack.push((target, 0));
+                            }
+                            Mark::InProgress => {
+                                let pos = stack.iter().position(|(n, _)| *n == target).unwrap();
+                                let mut slugs: Vec<String> = stack[pos..]
+                                    .iter()
+                                    .map(|(n, _)| self.nodes[*n].slug.clone())
+                                    .collect();
+                                slugs.push(self.nodes[target].slug.clone());
+                                cycles.push(GraphError::Cycle {
+                                    lang: self.nodes[target].language(),
+                                    slugs,
+                                });
+                            }
+                            Mark::Done => {}
+                        }
+                    }
+                    None => {
+                        marks[node] = Mark::Done;
+                        stack.pop();
+                    }
+                }
+            }
+        }
+        cycles
+    }
+
+    /// Computes an ordered learning path for a language, from beginner to advanced.
+    ///
+    /// Every cheat sheet comes after its prerequisites, and between cheat sheets that are free
+    /// to go next the lower level comes first, then the title alphabetically.
+    /// Dangling references are ignored, while a cycle makes the path impossible and is returned as an error.
+    pub fn learning_path(&self, lang: Language) -> Result<Vec<&'a Cheatsheet>, GraphError> {
+        let members: Vec<usize> = (0..self.nodes.len())
+            .filter(|i| self.nodes[*i].language() == lang)
+            .collect();
+
+        let mut in_degree = vec![0usize; self.nodes.len()];
+        for &i in &members {
+            for &j in &self.edges[i] {
+                in_degree[j] += 1;
+            }
+        }
+
+        let key = |i: usize| -> Reverse<(Level, String, usize)> {
+            Reverse((self.nodes[i].level(), self.nodes[i].title(), i))
+        };
+        let mut ready: BinaryHeap<_> = members
+            .iter()
+            .filter(|i| in_degree[**i] == 0)
+            .map(|i| key(*i))
+            .collect();
+
+        let mut path = Vec::with_capacity(members.len());
+        while let Some(Reverse((_, _, i))) = ready.pop() {
+            path.push(self.nodes[i]);
+            for &j in &self.edges[i] {
+                in_degree[j] -= 1;
+                if in_degree[j] == 0 {
+                    ready.push(key(j));
+                }
+            }
+        }
+
+        if path.len() < members.len() {
+            let cycle = self
+                .cycles()
+                .into_iter()
+                .find(|x| matches!(x, GraphError::Cycle { lang: l, .. } if *l == lang))
+                .unwrap();
+            return Err(cycle);
+        }
+        Ok(path)
+    }
+}
+
+impl Cacher {
+    /// Builds the learning graph over the cached cheat sheets
+    pub fn learning_graph(&self) -> LearningGraph<'_> {
+        LearningGraph::new(&self.cheatsheets)
+    }
+    /// Computes the ordered learning path for a language, see [`LearningGraph::learning_path`]
+    pub fn learning_path(&self, lang: Language) -> Result<Vec<&Cheatsheet>, GraphError> {
+        self.learning_graph().learning_path(lang)
+    }
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+/// The locale of resources that don't state one
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Normalises a locale into the form it's compared by,
+/// lowercase with `-` between its parts, e.g. `es_MX` becomes `es-mx`
+pub fn normalize(locale: &str) -> String {
+    locale.trim().replace('_', "-").to_lowercase()
+}
+
+/// The locales to try in order when looking for a resource in the locale:
+/// the locale itself, then each of its parents and finally [`DEFAULT_LOCALE`],
+/// e.g. `es-mx`, `es`, `en`
+pub fn fallbacks(locale: &str) -> Vec<String> {
+    let locale = normalize(locale);
+    let mut fallbacks = Vec::new();
+    let mut current = locale.as_str();
+    while !current.is_empty() {
+        fallbacks.push(current.to_string());
+        current = current.rsplit_once('-').map_or("", |(parent, _)| parent);
+    }
+    if !fallbacks.iter().any(|x| x == DEFAULT_LOCALE) {
+        fallbacks.push(DEFAULT_LOCALE.to_string());
+    }
+    fallbacks
+}
+
+/// A translation of a resource, to link as an alternate language version of it
+/// (like `<link rel="alternate" hreflang="es" href="...">`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Alternate {
+    pub locale: String,
+    pub link: String,
+}
+
+impl Alternate {
+    /// The html `<link>` tag of the alternate, with the locale and the link escaped
+    pub fn to_html(&self) -> String {
+        format!(
+            "<link rel=\"alternate\" hreflang=\"{}\" href=\"{}\">",
+            escape_attribute(&self.locale),
+            escape_attribute(&self.link)
+        )
+    }
+}
+
+fn escape_attribute(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
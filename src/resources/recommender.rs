@@ -0,0 +1,180 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::resources::searcher::analyzer::Analyzer;
+use crate::resources::searcher::Document;
+use crate::resources::{Cacher, Resource, SearchMetadata};
+
+/// How much sharing tags counts, scaled by the Jaccard similarity of the tag sets
+const TAG_WEIGHT: f32 = 0.3;
+/// How much being about the same language counts
+const LANG_WEIGHT: f32 = 0.2;
+/// How much having close levels counts, when both resources have a level
+const LEVEL_WEIGHT: f32 = 0.1;
+/// How much similar text counts, scaled by the TF-IDF cosine similarity
+const TEXT_WEIGHT: f32 = 0.4;
+
+/// A resource related to another one, with a similarity score between 0 and 1
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Recommendation {
+    pub metadata: SearchMetadata,
+    pub score: f32,
+}
+
+/// The features of a resource that are compared
+struct Item {
+    document: Document,
+    tags: Vec<String>,
+    /// The L2 normalised TF-IDF weights by term id, sorted by term id
+    vector: Vec<(u32, f32)>,
+}
+
+/// # Recommender
+///
+/// Finds the resources related to an article, cheatsheet or guide for "You might also like" links,
+/// scoring every pair by their shared tags, language, level and the TF-IDF cosine similarity
+/// of their title, description and content (analyzed with the default [`Analyzer`]).
+///
+/// Building it computes the vectors of every resource once, so keep it around
+/// rather than building one per page.
+pub struct Recommender {
+    items: Vec<Item>,
+    keys: HashMap<String, usize>,
+}
+
+impl Recommender {
+    /// Compares the visible resources of the cacher, see [`Cacher::visible_resources`]
+    pub fn new(cacher: &Cacher) -> Self {
+        let mut keys = HashMap::new();
+        let mut documents = Vec::new();
+        for resource in cacher.visible_resources() {
+            if let Entry::Vacant(entry) = keys.entry(resource.id()) {
+                entry.insert(documents.len());
+                documents.push(Document::new(resource));
+            }
+        }
+
+        // term frequencies by term id, and the number of documents with each term
+        let mut vocabulary: HashMap<String, u32> = HashMap::new();
+        let mut df: Vec<u32> = Vec::new();
+        let analyzer = Analyzer::default();
+        let counts: Vec<HashMap<u32, u32>> = documents
+            .iter()
+            .map(|doc| {
+                let text = [doc.meta.title(), &doc.description, &doc.content].join(" ");
+                let mut counts = HashMap::new();
+                for term in analyzer.analyze(&text) {
+                    let next = vocabulary.len() as u32;
+                    let id = *vocabulary.entry(term).or_insert(next);
+                    *counts.entry(id).or_default() += 1;
+                }
+                df.resize(vocabulary.len(), 0);
+                counts.keys().for_each(|id| df[*id as usize] += 1);
+                counts
+            })
+            .collect();
+
+        let n = documents.len() as f32;
+        let items = documents
+            .into_iter()
+            .zip(counts)
+            .map(|(document, counts)| {
+                let mut vector: Vec<(u32, f32)> = counts
+                    .into_iter()
+                    .map(|(id, tf)| {
+                        let idf = ((1.0 + n) / (1.0 + df[id as usize] as f32)).ln() + 1.0;
+                        (id, tf as f32 * idf)
+                    })
+                    .collect();
+                let norm = vector.iter().map(|(_, w)| w * w).sum::<f32>().sqrt();
+                if norm > 0.0 {
+                    vector.iter_mut().for_each(|(_, w)| *w /= norm);
+                }
+                vector.sort_by_key(|(id, _)| *id);
+                Item {
+                    tags: document.tag_keys(),
+                    document,
+                    vector,
+                }
+            })
+            .collect();
+
+        Self { items, keys }
+    }
+
+    /// The similarity of two resources between 0 and 1
+    fn similarity(a: &Item, b: &Item) -> f32 {
+        let shared = a.tags.iter().filter(|x| b.tags.contains(x)).count();
+        let union = a.tags.len() + b.tags.len() - shared;
+        let tags = if union == 0 {
+            0.0
+        } else {
+            shared as f32 / union as f32
+        };
+
+        let (a_doc, b_doc) = (&a.document, &b.document);
+        let lang = a_doc.langs.iter().any(|x| b_doc.langs.contains(x));
+        let level = match (a_doc.level, b_doc.level) {
+            (Some(x), Some(y)) => 1.0 - (x as u8).abs_diff(y as u8) as f32 / 2.0,
+            _ => 0.0,
+        };
+
+        TAG_WEIGHT * tags
+            + LANG_WEIGHT * f32::from(u8::from(lang))
+            + LEVEL_WEIGHT * level
+            + TEXT_WEIGHT * cosine(&a.vector, &b.vector)
+    }
+
+    /// The `n` resources most similar to the resource, best first. Resources that have nothing
+    /// in common with it are left out, and a resource that isn't in the cacher has no recommendations.
+    pub fn related(&self, resource: &(impl Resource + ?Sized), n: usize) -> Vec<Recommendation> {
+        let Some(&index) = self.keys.get(&resource.id()) else {
+            return Vec::new();
+        };
+        let item = &self.items[index];
+        let mut scores: Vec<(usize, f32)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != index)
+            .map(|(i, other)| (i, Self::similarity(item, other)))
+            .filter(|(_, score)| *score > 0.0)
+            .collect();
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        scores
+            .into_iter()
+            .take(n)
+            .map(|(i, score)| Recommendation {
+                metadata: self.items[i].document.meta.clone(),
+                score,
+            })
+            .collect()
+    }
+}
+
+/// The dot product of two normalised sparse vectors sorted by term id
+fn cosine(a: &[(u32, f32)], b: &[(u32, f32)]) -> f32 {
+    let (mut i, mut j, mut dot) = (0, 0, 0.0);
+    while i < a.len() && j < b.len() {
+        match a[i].0.cmp(&b[j].0) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                dot += a[i].1 * b[j].1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    dot
+}
+
+impl Cacher {
+    /// Builds the recommender over the cached resources, see [`Recommender::related`]
+    pub fn recommender(&self) -> Recommender {
+        Recommender::new(self)
+    }
+}
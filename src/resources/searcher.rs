@@ -0,0 +1,822 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::resources::cheatsheet::{Language, Level};
+use crate::resources::version::Version;
+use crate::resources::{Cacher, Resource, ResourceType};
+
+use analytics::{QueryKind, SearchObserver};
+use analyzer::Analyzer;
+use bm25::{Field, TextIndex};
+use facets::Facets;
+use page::{Cursor, SearchPage, SearchRequest, SortBy};
+use query::{Filter, Query, QueryError};
+use snippet::Snippet;
+use suggest::{Suggester, SuggestionKind};
+use text::{normalize, plain_text};
+
+/// Recording the queries made to find what learners miss
+pub mod analytics;
+/// Normalisation, stop words, stemming and synonyms of the indexed and searched text
+pub mod analyzer;
+/// BM25 ranked full-text index
+pub mod bm25;
+/// The compact export format for shipping the index to clients
+pub mod export;
+/// Result counts by language, type, level, tag and author
+pub mod facets;
+/// Sorted and paginated search requests
+pub mod page;
+/// Saving and loading built searchers in a binary format
+pub mod persist;
+/// Tag pages and author profiles
+pub mod profiles;
+/// The structured query language
+pub mod query;
+/// Highlighted excerpts of the matches in search results
+pub mod snippet;
+/// Prefix completion and fuzzy matching of titles and tags
+pub mod suggest;
+/// Plain text extraction and tokenisation
+pub mod text;
+
+/// Metadata for a search result
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SearchMetadata {
+    title: String,
+    ty: ResourceType,
+    link: String,
+    lang: String,
+    /// The language version the resource targets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+}
+
+impl SearchMetadata {
+    pub fn new(title: String, ty: ResourceType, link: String, lang: String) -> Self {
+        Self {
+            title,
+            ty,
+            link,
+            lang,
+            version: None,
+        }
+    }
+    /// Sets the language version the resource targets
+    pub fn with_version(mut self, version: Option<String>) -> Self {
+        self.version = version;
+        self
+    }
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+    pub fn ty(&self) -> ResourceType {
+        self.ty
+    }
+    pub fn link(&self) -> &str {
+        &self.link
+    }
+    /// Checks if the resource targets at least the `min` language version,
+    /// resources without a version apply to any version
+    pub fn targets_at_least(&self, min: &Version) -> bool {
+        match self.version.as_deref().map(Version::from_str) {
+            Some(Ok(version)) => version >= *min,
+            _ => true,
+        }
+    }
+}
+
+/// The attributes of an indexed resource that queries can filter on
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(super) struct Document {
+    /// The key of the resource in [`Searcher::keys`]
+    pub(super) key: String,
+    pub(super) meta: SearchMetadata,
+    pub(super) langs: Vec<Language>,
+    pub(super) level: Option<Level>,
+    pub(super) author: String,
+    pub(super) tags: Vec<String>,
+    /// The plain text of the description, used for snippets
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub(super) description: String,
+    /// The plain text of the content, used for snippets
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub(super) content: String,
+    /// The publication date (YYYY-MM-DD), used for sorting
+    pub(super) date: Option<String>,
+}
+
+impl Document {
+    pub(super) fn new(resource: &(impl Resource + ?Sized)) -> Self {
+        Self {
+            key: resource.id(),
+            meta: resource.as_search_meta(),
+            langs: resource.languages(),
+            level: resource.difficulty(),
+            author: resource.author(),
+            tags: resource.tags(),
+            description: resource.description(),
+            content: plain_text(resource.content()),
+            date: resource.published(),
+        }
+    }
+
+    /// The analyzed text of the document for the full-text index
+    fn fields(&self, analyzer: &Analyzer) -> Vec<(Field, Vec<String>)> {
+        let mut fields = vec![
+            (Field::Title, analyzer.analyze(self.meta.title())),
+            (Field::Description, analyzer.analyze(&self.description)),
+        ];
+        fields.extend(self.tags.iter().map(|x| (Field::Tags, analyzer.analyze(x))));
+        fields.push((Field::Content, analyzer.analyze(&self.content)));
+        fields
+    }
+
+    /// The best snippet of the content for the terms, then of the description,
+    /// falling back to the start of the description when neither contains them
+    fn snippet(&self, terms: &[String], analyzer: &Analyzer) -> Option<Snippet> {
+        Snippet::around(&self.content, terms, analyzer)
+            .or_else(|| Snippet::around(&self.description, terms, analyzer))
+            .or_else(|| Snippet::lead(&self.description))
+            .or_else(|| Snippet::lead(&self.content))
+    }
+
+    /// The distinct normalised tags of the document
+    pub(super) fn tag_keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.tags.iter().map(|x| normalize(x)).collect();
+        keys.retain(|x| !x.is_empty());
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+
+    fn author_key(&self) -> Option<String> {
+        Some(normalize(&self.author)).filter(|x| !x.is_empty())
+    }
+
+    /// The titles and tags the document can be suggested for
+    fn suggestions(&self) -> impl Iterator<Item = (SuggestionKind, &str)> {
+        std::iter::once((SuggestionKind::Title, self.meta.title()))
+            .chain(self.tags.iter().map(|x| (SuggestionKind::Tag, x.as_str())))
+    }
+}
+
+/// A search result with its relevance score, higher is better
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SearchHit {
+    pub metadata: SearchMetadata,
+    pub score: f32,
+    /// Where the resource matched the search, see [`Snippet`]
+    pub snippet: Option<Snippet>,
+}
+
+/// Removes a document from the ids of a key, dropping the key once it has no documents
+fn unlink<K: Eq + Hash>(map: &mut HashMap<K, Vec<u32>>, key: &K, doc: u32) {
+    if let Some(ids) = map.get_mut(key) {
+        ids.retain(|x| *x != doc);
+        if ids.is_empty() {
+            map.remove(key);
+        }
+    }
+}
+
+/// The ids in both sorted lists
+fn intersection(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (mut i, mut j) = (0, 0);
+    let mut ids = Vec::with_capacity(a.len().min(b.len()));
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                ids.push(a[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    ids
+}
+
+/// The ids in either sorted list, sorted
+fn union(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (mut i, mut j) = (0, 0);
+    let mut ids = Vec::with_capacity(a.len() + b.len());
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            Ordering::Less => {
+                ids.push(a[i]);
+                i += 1;
+            }
+            Ordering::Greater => {
+                ids.push(b[j]);
+                j += 1;
+            }
+            Ordering::Equal => {
+                ids.push(a[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    ids.extend_from_slice(&a[i..]);
+    ids.extend_from_slice(&b[j..]);
+    ids
+}
+
+/// The ids of the sorted list `a` that aren't in the sorted list `b`
+fn difference(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut j = 0;
+    a.iter()
+        .copied()
+        .filter(|id| {
+            while j < b.len() && b[j] < *id {
+                j += 1;
+            }
+            b.get(j) != Some(id)
+        })
+        .collect()
+}
+
+/// The results of a faceted search, see [`Searcher::faceted`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SearchResponse {
+    pub hits: Vec<SearchHit>,
+    pub facets: Facets,
+}
+
+/// A suggested title or tag with the resources it leads to
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Suggestion {
+    pub text: String,
+    pub kind: SuggestionKind,
+    /// The edit distance from the input, `0` for a prefix completion
+    pub distance: usize,
+    pub resources: Vec<SearchMetadata>,
+}
+
+/// The time [`Searcher::suggest`] may spend on fuzzy matching
+const SUGGEST_BUDGET: Duration = Duration::from_millis(5);
+
+/// Hashmaps for quick navigation
+///
+/// A user will be able to search for something under the following conditions:
+/// - Language of Focus
+/// - Title of Resource
+/// - Resource Type
+/// - Tag and Author (see [`Searcher::tag`] and [`Searcher::author`])
+/// - Full text of the title, description, tags and content (see [`Searcher::full_text`])
+///
+/// The hashmaps point to document ids, and resources can be inserted, updated or removed
+/// one at a time without rebuilding the searcher. Removed documents leave an empty slot
+/// so the ids of the others don't change, until they're more than half of the slots and
+/// the documents are renumbered (see [`Searcher::compact`]).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Searcher {
+    langs: HashMap<Language, Vec<u32>>,
+    titles: HashMap<String, Vec<u32>>,
+    rty: HashMap<ResourceType, Vec<u32>>,
+    /// By normalised tag
+    tags: HashMap<String, Vec<u32>>,
+    /// By normalised author name
+    authors: HashMap<String, Vec<u32>>,
+    /// Every indexed resource, indexed by its document id in `text`
+    docs: Vec<Option<Document>>,
+    /// The document id of every resource key
+    keys: HashMap<String, u32>,
+    text: TextIndex,
+    suggester: Suggester,
+    /// How the text of the resources and the queries is turned into terms
+    analyzer: Analyzer,
+    /// Whether drafts and scheduled resources are indexed, see [`Cacher::is_preview`]
+    #[serde(default)]
+    preview: bool,
+    /// The timestamp the next scheduled resource left out is published at
+    #[serde(default)]
+    expires_at: Option<i64>,
+    /// Records the queries for analytics
+    #[serde(skip)]
+    observer: Option<Arc<dyn SearchObserver>>,
+}
+
+impl Searcher {
+    /// Indexes the visible resources of the cacher (see [`Cacher::visible_resources`]),
+    /// analyzing their text with the English [`Analyzer`]
+    ///
+    /// The resources scheduled after now are left out until the searcher is rebuilt once
+    /// they're published, see [`Searcher::refresh`].
+    pub fn new(cacher: &Cacher) -> Self {
+        Self::with_analyzer(cacher, Analyzer::default())
+    }
+
+    /// Indexes the visible resources of the cacher, analyzing their text and the queries with the analyzer
+    pub fn with_analyzer(cacher: &Cacher, analyzer: Analyzer) -> Self {
+        let now = Utc::now();
+        let mut searcher = Self::empty(analyzer);
+        searcher.preview = cacher.is_preview();
+        if !searcher.preview {
+            searcher.expires_at = cacher.next_publish_at(now).map(|x| x.timestamp());
+        }
+        for resource in cacher.visible_resources_at(now) {
+            if !searcher.keys.contains_key(&resource.id()) {
+                searcher.add(resource);
+            }
+        }
+
+        searcher.suggester = searcher.build_suggester();
+        searcher
+    }
+
+    /// A searcher without any resources
+    fn empty(analyzer: Analyzer) -> Self {
+        Self {
+            langs: HashMap::new(),
+            titles: HashMap::new(),
+            rty: HashMap::new(),
+            tags: HashMap::new(),
+            authors: HashMap::new(),
+            docs: Vec::new(),
+            keys: HashMap::new(),
+            text: TextIndex::new(),
+            suggester: Suggester::default(),
+            analyzer,
+            preview: false,
+            expires_at: None,
+            observer: None,
+        }
+    }
+
+    /// The time the next scheduled resource left out of the searcher is published at, after
+    /// which the searcher is out of date, `None` if no resource is scheduled
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_at
+            .and_then(|x| Utc.timestamp_opt(x, 0).single())
+    }
+
+    /// Rebuilds the searcher from the cacher once a scheduled resource left out of it is
+    /// published, keeping its analyzer and observer. Returns whether it was rebuilt.
+    pub fn refresh(&mut self, cacher: &Cacher) -> bool {
+        if self.expires_at().is_none_or(|x| x > Utc::now()) {
+            return false;
+        }
+        let observer = self.observer.take();
+        *self = Self::with_analyzer(cacher, self.analyzer.clone());
+        self.observer = observer;
+        true
+    }
+
+    /// Records every search, full-text search, query and request made on the searcher with the
+    /// observer (see [`analytics`]). Suggestions aren't recorded as they're made per keystroke.
+    pub fn with_observer(mut self, observer: Arc<dyn SearchObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    pub fn analyzer(&self) -> &Analyzer {
+        &self.analyzer
+    }
+
+    /// A searcher of the documents with the text index already made for them,
+    /// numbering the documents in order
+    fn from_parts(analyzer: Analyzer, text: TextIndex, docs: Vec<Document>) -> Self {
+        let mut searcher = Self::empty(analyzer);
+        searcher.text = text;
+        for (doc, document) in docs.into_iter().enumerate() {
+            searcher.register(doc as u32, document);
+        }
+        searcher.suggester = searcher.build_suggester();
+        searcher
+    }
+
+    /// The new id of every document once the empty slots are dropped, `None` for the empty slots
+    fn live_ids(&self) -> Vec<Option<u32>> {
+        let mut ids = Vec::with_capacity(self.docs.len());
+        let mut next = 0;
+        for doc in &self.docs {
+            ids.push(doc.as_ref().map(|_| next));
+            next += u32::from(doc.is_some());
+        }
+        ids
+    }
+
+    /// Drops the empty slots left by removed resources, renumbering the documents.
+    ///
+    /// This is done on removal once the empty slots are more than half of the slots,
+    /// so it only needs to be called to reclaim the memory sooner.
+    pub fn compact(&mut self) {
+        if self.docs.len() == self.keys.len() {
+            return;
+        }
+        let postings = self.text.encode(&self.live_ids());
+        let text =
+            TextIndex::decode(&postings, self.keys.len()).expect("the postings were just encoded");
+        let docs = std::mem::take(&mut self.docs)
+            .into_iter()
+            .flatten()
+            .collect();
+        let (preview, expires_at) = (self.preview, self.expires_at);
+        let observer = self.observer.take();
+        *self = Self::from_parts(self.analyzer.clone(), text, docs);
+        self.preview = preview;
+        self.expires_at = expires_at;
+        self.observer = observer;
+    }
+
+    /// Generates the suggestions from the titles and tags all at once, as sorting them
+    /// is cheaper than inserting them one by one
+    fn build_suggester(&self) -> Suggester {
+        Suggester::new(self.docs.iter().enumerate().flat_map(|(i, x)| {
+            x.iter()
+                .flat_map(|doc| doc.suggestions())
+                .map(move |(kind, text)| (kind, text, i as u32))
+        }))
+    }
+
+    /// Adds a resource to every index except the suggester
+    fn add(&mut self, resource: &(impl Resource + ?Sized)) -> u32 {
+        let document = Document::new(resource);
+        let doc = self.text.add(&document.fields(&self.analyzer));
+        self.register(doc, document);
+        doc
+    }
+
+    /// Adds a document to the hashmaps, its text must already be in the text index under `doc`
+    fn register(&mut self, doc: u32, document: Document) {
+        debug_assert_eq!(doc as usize, self.docs.len());
+        for lang in &document.langs {
+            self.langs.entry(*lang).or_default().push(doc);
+        }
+        self.titles
+            .entry(document.meta.title.clone())
+            .or_default()
+            .push(doc);
+        self.rty.entry(document.meta.ty).or_default().push(doc);
+        for tag in document.tag_keys() {
+            self.tags.entry(tag).or_default().push(doc);
+        }
+        if let Some(author) = document.author_key() {
+            self.authors.entry(author).or_default().push(doc);
+        }
+        self.keys.insert(document.key.clone(), doc);
+        self.docs.push(Some(document));
+    }
+
+    /// Indexes a resource, replacing the indexed resource with the same [`id`](Resource::id)
+    /// if there is one. Returns whether a resource was replaced.
+    ///
+    /// Unless the searcher was built in preview mode, a resource that isn't published now is
+    /// only removed, like when the searcher is built.
+    pub fn insert(&mut self, resource: &(impl Resource + ?Sized)) -> bool {
+        let replaced = self.remove(resource).is_some();
+        if !self.preview && !resource.is_published_at(Utc::now()) {
+            if let Some(at) = resource.publish_at().filter(|_| !resource.is_draft()) {
+                let at = at.timestamp();
+                self.expires_at = Some(self.expires_at.map_or(at, |x| x.min(at)));
+            }
+            return replaced;
+        }
+        let doc = self.add(resource);
+        let document = self.docs[doc as usize].as_ref().unwrap();
+        for (kind, text) in document.suggestions() {
+            self.suggester.insert(kind, text, doc);
+        }
+        replaced
+    }
+
+    /// Replaces an indexed resource with its new version, returns `false` (without indexing it)
+    /// if the resource wasn't indexed. Like [`Searcher::insert`], a version that isn't published
+    /// now only removes the resource.
+    pub fn update(&mut self, resource: &(impl Resource + ?Sized)) -> bool {
+        if !self.keys.contains_key(&resource.id()) {
+            return false;
+        }
+        self.insert(resource)
+    }
+
+    /// Removes a resource from every index, returning its search metadata if it was indexed
+    pub fn remove(&mut self, resource: &(impl Resource + ?Sized)) -> Option<SearchMetadata> {
+        let doc = self.keys.remove(&resource.id())?;
+        let document = self.docs[doc as usize].take()?;
+
+        for lang in &document.langs {
+            unlink(&mut self.langs, lang, doc);
+        }
+        unlink(&mut self.titles, &document.meta.title, doc);
+        unlink(&mut self.rty, &document.meta.ty, doc);
+        for tag in document.tag_keys() {
+            unlink(&mut self.tags, &tag, doc);
+        }
+        if let Some(author) = document.author_key() {
+            unlink(&mut self.authors, &author, doc);
+        }
+        for (kind, text) in document.suggestions() {
+            self.suggester.remove(kind, text, doc);
+        }
+        self.text.remove(doc);
+        if self.docs.len() - self.keys.len() > self.keys.len() {
+            self.compact();
+        }
+        Some(document.meta)
+    }
+
+    /// The number of indexed resources
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The search metadata of the documents that are still indexed
+    fn metas(&self, docs: &[u32]) -> Vec<SearchMetadata> {
+        docs.iter()
+            .filter_map(|doc| self.docs[*doc as usize].as_ref())
+            .map(|x| x.meta.clone())
+            .collect()
+    }
+
+    /// Searches for an exact title, a language (or a synonym of one, like `golang`) or a resource type,
+    /// falling back to the full-text results when the input is none of these.
+    ///
+    /// The resources of a language list its cheatsheets from beginner to advanced first,
+    /// followed by the articles tagged with the language.
+    pub fn search(&self, input: String) -> Vec<SearchMetadata> {
+        let run = || self.lookup(&input);
+        self.observe(QueryKind::Search, &input, run, |x| Some(x.len()))
+    }
+
+    fn lookup(&self, input: &str) -> Vec<SearchMetadata> {
+        let lowercase_input = input.to_lowercase();
+        let lang_input = Language::from_str(self.analyzer.synonym(&lowercase_input));
+        if let Some(docs) = self.titles.get(input) {
+            return self.metas(docs);
+        } else if lang_input != Language::Other {
+            if let Some(docs) = self.langs.get(&lang_input) {
+                let mut docs = docs.clone();
+                docs.sort_by_key(|doc| {
+                    let document = self.docs[*doc as usize].as_ref();
+                    document.map(|x| (x.meta.ty != ResourceType::Cheatsheet, x.level))
+                });
+                return self.metas(&docs);
+            }
+        } else if let Some(docs) = ResourceType::from_str(&lowercase_input)
+            .ok()
+            .and_then(|ty| self.rty.get(&ty))
+        {
+            return self.metas(docs);
+        }
+
+        self.full_text_hits(input)
+            .into_iter()
+            .map(|hit| hit.metadata)
+            .collect()
+    }
+
+    /// Suggests up to `limit` titles and tags as the user types, completing the input as a prefix and
+    /// tolerating typos in its last word, see [`Searcher::suggest_within`] to set the time budget
+    pub fn suggest(&self, input: &str, limit: usize) -> Vec<Suggestion> {
+        self.suggest_within(input, limit, SUGGEST_BUDGET)
+    }
+
+    /// Suggests titles and tags like [`Searcher::suggest`], spending at most `budget` on fuzzy matching
+    pub fn suggest_within(&self, input: &str, limit: usize, budget: Duration) -> Vec<Suggestion> {
+        self.suggester
+            .suggest(input, limit, budget)
+            .into_iter()
+            .map(|x| Suggestion {
+                text: x.text,
+                kind: x.kind,
+                distance: x.distance,
+                resources: self.metas(&x.docs),
+            })
+            .collect()
+    }
+
+    /// Ranks the resources matching any term of the query using BM25 over their titles,
+    /// descriptions, tags and content, best match first
+    pub fn full_text(&self, query: &str) -> Vec<SearchHit> {
+        let run = || self.full_text_hits(query);
+        self.observe(QueryKind::FullText, query, run, |x| Some(x.len()))
+    }
+
+    fn full_text_hits(&self, query: &str) -> Vec<SearchHit> {
+        let terms = self.analyzer.analyze(query);
+        self.hits(self.text.search(&terms), &terms)
+    }
+
+    fn hits(&self, scores: Vec<(u32, f32)>, terms: &[String]) -> Vec<SearchHit> {
+        scores
+            .into_iter()
+            .filter_map(|(doc, score)| {
+                let document = self.docs[doc as usize].as_ref()?;
+                Some(SearchHit {
+                    metadata: document.meta.clone(),
+                    score,
+                    snippet: document.snippet(terms, &self.analyzer),
+                })
+            })
+            .collect()
+    }
+
+    /// Searches with a structured query (see [`Query`]), e.g. `lang:rust type:cheatsheet ownership`.
+    ///
+    /// The matching resources are ranked with BM25 over the terms and phrases that aren't negated,
+    /// and resources only matched through filters keep the order they were indexed in.
+    pub fn query(&self, input: &str) -> Result<Vec<SearchHit>, QueryError> {
+        let query: Query = input.parse()?;
+        let run = || self.run_query(&query);
+        Ok(self.observe(QueryKind::Query, input, run, |x| Some(x.len())))
+    }
+
+    /// Runs a parsed query, see [`Searcher::query`]
+    pub fn run_query(&self, query: &Query) -> Vec<SearchHit> {
+        let (ranked, terms) = self.rank(query);
+        self.hits(ranked, &terms)
+    }
+
+    /// Searches with a structured query like [`Searcher::query`], narrowed down by the facet
+    /// filters the user picked, and counts the facets of the results.
+    /// An empty query matches every resource.
+    pub fn faceted(
+        &self,
+        input: &str,
+        drill_down: &[Filter],
+    ) -> Result<SearchResponse, QueryError> {
+        let mut parts = Vec::new();
+        if !input.trim().is_empty() {
+            parts.push(input.parse()?);
+        }
+        parts.extend(drill_down.iter().cloned().map(Query::Filter));
+        let run = || self.run_faceted(&Query::And(parts));
+        Ok(self.observe(QueryKind::Faceted, input, run, |x| Some(x.hits.len())))
+    }
+
+    /// Runs a parsed query and counts the facets of the results, see [`Searcher::faceted`]
+    pub fn run_faceted(&self, query: &Query) -> SearchResponse {
+        let (ranked, terms) = self.rank(query);
+        let facets = Facets::count(
+            ranked
+                .iter()
+                .filter_map(|(doc, _)| self.docs[*doc as usize].as_ref()),
+        );
+        SearchResponse {
+            hits: self.hits(ranked, &terms),
+            facets,
+        }
+    }
+
+    /// Runs a [`SearchRequest`], returning the requested page of hits sorted by the requested order,
+    /// with the total number of hits and the facets of all of them.
+    ///
+    /// Only the hits on the page are cloned and get a snippet.
+    pub fn execute(&self, request: &SearchRequest) -> Result<SearchPage, QueryError> {
+        let run = || self.run_request(request);
+        let total = |x: &Result<SearchPage, QueryError>| x.as_ref().ok().map(|x| x.total);
+        self.observe(QueryKind::Request, &request.query, run, total)
+    }
+
+    fn run_request(&self, request: &SearchRequest) -> Result<SearchPage, QueryError> {
+        let mut parts = Vec::new();
+        if !request.query.trim().is_empty() {
+            parts.push(request.query.parse()?);
+        }
+        parts.extend(request.filters.iter().cloned().map(Query::Filter));
+        let (mut ranked, terms) = self.rank(&Query::And(parts));
+
+        // the ranking is already by relevance, and the sorts are stable so ties stay by relevance
+        let document = |doc: u32| self.docs[doc as usize].as_ref().unwrap();
+        match request.sort {
+            SortBy::Relevance => {}
+            SortBy::Date => ranked.sort_by(|a, b| {
+                let (a, b) = (&document(a.0).date, &document(b.0).date);
+                // newest first with the undated last, as `None` is the smallest
+                b.cmp(a)
+            }),
+            SortBy::Title => ranked.sort_by_cached_key(|x| document(x.0).meta.title.to_lowercase()),
+            SortBy::Level => {
+                ranked.sort_by_key(|x| (document(x.0).level.is_none(), document(x.0).level))
+            }
+        }
+
+        let offset = match &request.cursor {
+            Some(cursor) => ranked
+                .iter()
+                .position(|(doc, _)| document(*doc).key == cursor.key)
+                .map_or(cursor.offset, |i| i + 1),
+            None => request.offset,
+        }
+        .min(ranked.len());
+        let end = offset.saturating_add(request.limit).min(ranked.len());
+        let next = (end < ranked.len() && end > offset).then(|| Cursor {
+            offset: end,
+            key: document(ranked[end - 1].0).key.clone(),
+        });
+
+        Ok(SearchPage {
+            total: ranked.len(),
+            offset,
+            next,
+            facets: Facets::count(ranked.iter().map(|(doc, _)| document(*doc))),
+            hits: self.hits(ranked[offset..end].to_vec(), &terms),
+        })
+    }
+
+    /// The documents matching the query, best match first, with the terms they were ranked by
+    fn rank(&self, query: &Query) -> (Vec<(u32, f32)>, Vec<String>) {
+        let matched = self.evaluate(query);
+        let terms: Vec<String> = query
+            .positive_text()
+            .iter()
+            .flat_map(|x| self.analyzer.analyze(x))
+            .collect();
+        let scores = self.text.scores(&terms);
+
+        let mut hits: Vec<(u32, f32)> = matched
+            .into_iter()
+            .map(|doc| (doc, scores.get(&doc).copied().unwrap_or(0.0)))
+            .collect();
+        hits.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        (hits, terms)
+    }
+
+    /// The ids of the indexed documents matching the query, in increasing order
+    fn evaluate(&self, query: &Query) -> Vec<u32> {
+        let indexed = || self.matching(|_| true);
+        match query {
+            Query::Term(text) | Query::Phrase(text) => {
+                let terms = self.analyzer.analyze(text);
+                // only stop words, which every resource is assumed to contain
+                if terms.is_empty() {
+                    return indexed();
+                }
+                self.text.docs_with_phrase(&terms)
+            }
+            Query::Filter(filter) => self.filtered(filter),
+            Query::Not(query) => difference(&indexed(), &self.evaluate(query)),
+            Query::And(parts) => parts
+                .iter()
+                .map(|x| self.evaluate(x))
+                .reduce(|a, b| intersection(&a, &b))
+                .unwrap_or_else(indexed),
+            Query::Or(parts) => parts
+                .iter()
+                .map(|x| self.evaluate(x))
+                .reduce(|a, b| union(&a, &b))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The ids of the indexed documents matching the filter, in increasing order, looked up in
+    /// the hashmaps except for the levels, which aren't indexed
+    fn filtered(&self, filter: &Filter) -> Vec<u32> {
+        fn posting<K: Eq + Hash>(map: &HashMap<K, Vec<u32>>, key: &K) -> Vec<u32> {
+            let mut ids = map.get(key).cloned().unwrap_or_default();
+            ids.dedup();
+            ids
+        }
+        match filter {
+            Filter::Lang(lang) => posting(&self.langs, lang),
+            Filter::Type(ty) => posting(&self.rty, ty),
+            Filter::Tag(tag) => posting(&self.tags, tag),
+            Filter::Author(author) => posting(&self.authors, &normalize(author)),
+            Filter::Title(title) => {
+                let mut ids: Vec<u32> = self
+                    .titles
+                    .iter()
+                    .filter(|(x, _)| x.to_lowercase().contains(title))
+                    .flat_map(|(_, ids)| ids.iter().copied())
+                    .collect();
+                ids.sort_unstable();
+                ids.dedup();
+                ids
+            }
+            Filter::Level(level) => self.matching(|x| x.level == Some(*level)),
+        }
+    }
+
+    /// The ids of the indexed documents the predicate holds for, in increasing order
+    fn matching(&self, predicate: impl Fn(&Document) -> bool) -> Vec<u32> {
+        self.docs
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| x.as_ref().is_some_and(&predicate))
+            .map(|(doc, _)| doc as u32)
+            .collect()
+    }
+
+    /// Searches like [`Searcher::search`], leaving out resources that target a language version older than `min`
+    pub fn search_since(&self, input: String, min: &Version) -> Vec<SearchMetadata> {
+        self.search(input)
+            .into_iter()
+            .filter(|x| x.targets_at_least(min))
+            .collect()
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::Searcher;
+
+/// The search method a query was made with
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum QueryKind {
+    Search,
+    FullText,
+    Query,
+    Faceted,
+    Request,
+}
+
+/// A query made on a [`Searcher`] with an observer
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct QueryEvent {
+    pub query: String,
+    pub kind: QueryKind,
+    /// The number of resources found, across every page for paginated requests
+    pub results: usize,
+    pub latency_micros: u64,
+    /// When the query was made, in RFC 3339
+    pub at: String,
+}
+
+impl QueryEvent {
+    pub fn new(kind: QueryKind, query: &str, results: usize, latency: Duration) -> Self {
+        Self {
+            query: query.to_string(),
+            kind,
+            results,
+            latency_micros: latency.as_micros() as u64,
+            at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// # Search Observer
+///
+/// Receives every query made on a [`Searcher`] it's attached to with [`Searcher::with_observer`].
+/// It's called while searching, so recording should be quick.
+pub trait SearchObserver: Debug + Send + Sync {
+    fn record(&self, event: &QueryEvent);
+}
+
+/// Keeps the latest events in memory, dropping the oldest once it's full
+#[derive(Debug)]
+pub struct RingBuffer {
+    capacity: usize,
+    events: Mutex<VecDeque<QueryEvent>>,
+}
+
+impl RingBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            events: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+    /// The recorded events, oldest first
+    pub fn events(&self) -> Vec<QueryEvent> {
+        self.events.lock().unwrap().iter().cloned().collect()
+    }
+    /// The report of the recorded events, see [`Report`]
+    pub fn report(&self, top: usize) -> Report {
+        Report::new(&self.events(), top)
+    }
+}
+
+impl SearchObserver for RingBuffer {
+    fn record(&self, event: &QueryEvent) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut events = self.events.lock().unwrap();
+        if events.len() == self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event.clone());
+    }
+}
+
+/// Appends every event to a file as a line of JSON
+#[derive(Debug)]
+pub struct JsonLinesSink {
+    file: Mutex<File>,
+}
+
+impl JsonLinesSink {
+    /// Opens the file for appending, creating it if needed
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+    /// Reads the events of a file written by the sink, skipping lines that aren't events
+    pub fn read(path: impl AsRef<Path>) -> std::io::Result<Vec<QueryEvent>> {
+        let mut events = Vec::new();
+        for line in BufReader::new(File::open(path)?).lines() {
+            if let Ok(event) = serde_json::from_str(&line?) {
+                events.push(event);
+            }
+        }
+        Ok(events)
+    }
+}
+
+impl SearchObserver for JsonLinesSink {
+    fn record(&self, event: &QueryEvent) {
+        // analytics must never break searching, so failed writes are dropped
+        if let Ok(mut line) = serde_json::to_string(event) {
+            line.push('\n');
+            let _ = self.file.lock().unwrap().write_all(line.as_bytes());
+        }
+    }
+}
+
+/// # Report
+///
+/// What learners search for, and what they search for but don't find, to guide what to write next.
+/// Queries are grouped ignoring case and spacing.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Report {
+    pub queries: usize,
+    pub zero_results: usize,
+    pub average_latency_micros: u64,
+    /// The most common queries with how often they were made, most common first
+    pub top_queries: Vec<(String, usize)>,
+    /// The most common queries that found nothing
+    pub top_zero_results: Vec<(String, usize)>,
+}
+
+impl Report {
+    /// Summarises the events, keeping the `top` most common queries of each list
+    pub fn new(events: &[QueryEvent], top: usize) -> Self {
+        let mut queries: HashMap<String, usize> = HashMap::new();
+        let mut zero_results: HashMap<String, usize> = HashMap::new();
+        for event in events {
+            let query = event
+                .query
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join(" ")
+                .to_lowercase();
+            if event.results == 0 {
+                *zero_results.entry(query.clone()).or_default() += 1;
+            }
+            *queries.entry(query).or_default() += 1;
+        }
+        let total_latency: u64 = events.iter().map(|x| x.latency_micros).sum();
+
+        Self {
+            queries: events.len(),
+            zero_results: events.iter().filter(|x| x.results == 0).count(),
+            average_latency_micros: total_latency.checked_div(events.len() as u64).unwrap_or(0),
+            top_queries: most_common(queries, top),
+            top_zero_results: most_common(zero_results, top),
+        }
+    }
+}
+
+fn most_common(counts: HashMap<String, usize>, top: usize) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.truncate(top);
+    counts
+}
+
+impl Searcher {
+    /// Runs a search, recording it with the observer if there is one and `results` counts it
+    pub(super) fn observe<T>(
+        &self,
+        kind: QueryKind,
+        query: &str,
+        run: impl FnOnce() -> T,
+        results: impl FnOnce(&T) -> Option<usize>,
+    ) -> T {
+        let Some(observer) = &self.observer else {
+            return run();
+        };
+        let started = Instant::now();
+        let output = run();
+        if let Some(results) = results(&output) {
+            observer.record(&QueryEvent::new(kind, query, results, started.elapsed()));
+        }
+        output
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use rust_stemmers::{Algorithm, Stemmer};
+use serde::{Deserialize, Serialize};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use super::text::tokens;
+
+/// Words too common to tell resources apart, the English stop words of Lucene
+pub const ENGLISH_STOP_WORDS: [&str; 33] = [
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "if", "in", "into", "is", "it",
+    "no", "not", "of", "on", "or", "such", "that", "the", "their", "then", "there", "these",
+    "they", "this", "to", "was", "will", "with",
+];
+
+/// Alternative names of languages and tools, mapped to the name they're indexed by
+pub const SYNONYMS: [(&str, &str); 9] = [
+    ("golang", "go"),
+    ("cpp", "c++"),
+    ("cplusplus", "c++"),
+    ("csharp", "c#"),
+    ("js", "javascript"),
+    ("ts", "typescript"),
+    ("py", "python"),
+    ("rs", "rust"),
+    ("kt", "kotlin"),
+];
+
+/// # Analyzer
+///
+/// Turns text into the terms the [`Searcher`](super::Searcher) indexes and searches for,
+/// applied the same way to the resources and to the queries so they meet in the middle:
+///
+/// 1. Splitting into words and case folding (see [`tokenize`](super::text::tokenize))
+/// 2. Unicode normalisation, `ﬁle` becomes `file` and `café` becomes `cafe`
+/// 3. Synonyms, `golang` becomes `go`
+/// 4. Stop word removal, `the` and `of` are dropped
+/// 5. Stemming with the Snowball English stemmer, `closures` becomes `closur` like `closure`.
+///    Words with digits or symbols such as `c++` and `utf8` are kept whole.
+///
+/// Every step but the first can be turned off or configured with the `with_*` methods.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Analyzer {
+    unicode: bool,
+    synonyms: HashMap<String, String>,
+    stop_words: HashSet<String>,
+    stemming: bool,
+}
+
+impl Default for Analyzer {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+impl Analyzer {
+    /// The full chain for English text, with the default stop words and synonyms
+    pub fn english() -> Self {
+        Self::plain()
+            .with_unicode_normalization(true)
+            .with_stop_words(&ENGLISH_STOP_WORDS)
+            .with_stemming(true)
+            .with_synonyms(&SYNONYMS)
+    }
+
+    /// Only splits words and folds their case
+    pub fn plain() -> Self {
+        Self {
+            unicode: false,
+            synonyms: HashMap::new(),
+            stop_words: HashSet::new(),
+            stemming: false,
+        }
+    }
+
+    pub fn with_unicode_normalization(mut self, unicode: bool) -> Self {
+        self.unicode = unicode;
+        self
+    }
+
+    /// Replaces the stop words, an empty list keeps every word
+    pub fn with_stop_words(mut self, words: &[&str]) -> Self {
+        self.stop_words = words.iter().map(|x| x.to_lowercase()).collect();
+        self
+    }
+
+    pub fn with_stemming(mut self, stemming: bool) -> Self {
+        self.stemming = stemming;
+        self
+    }
+
+    /// Adds single word synonyms, every `from` is searched and indexed as its `to`
+    pub fn with_synonyms(mut self, synonyms: &[(&str, &str)]) -> Self {
+        for (from, to) in synonyms {
+            self.synonyms.insert(from.to_lowercase(), to.to_lowercase());
+        }
+        self
+    }
+
+    /// The name a lowercase word is indexed by, the word itself when it has no synonym
+    pub fn synonym<'a>(&'a self, word: &'a str) -> &'a str {
+        self.synonyms.get(word).map_or(word, |x| x.as_str())
+    }
+
+    /// The terms of the text
+    pub fn analyze(&self, text: &str) -> Vec<String> {
+        self.tokens(text)
+            .into_iter()
+            .map(|(_, term)| term)
+            .collect()
+    }
+
+    /// The terms of the text with the byte range of the word each comes from
+    pub fn tokens(&self, text: &str) -> Vec<(Range<usize>, String)> {
+        let stemmer = self.stemming.then(|| Stemmer::create(Algorithm::English));
+        tokens(text)
+            .into_iter()
+            .filter_map(|(range, word)| Some((range, self.term(word, stemmer.as_ref())?)))
+            .collect()
+    }
+
+    /// Runs a lowercase word through the chain, `None` if it's a stop word
+    fn term(&self, word: String, stemmer: Option<&Stemmer>) -> Option<String> {
+        let mut word = if self.unicode && !word.is_ascii() {
+            word.nfkd()
+                .filter(|c| !is_combining_mark(*c))
+                .collect::<String>()
+                .to_lowercase()
+        } else {
+            word
+        };
+        if let Some(synonym) = self.synonyms.get(&word) {
+            word = synonym.clone();
+        }
+        if self.stop_words.contains(&word) {
+            return None;
+        }
+        match stemmer {
+            Some(stemmer) if word.chars().all(char::is_alphabetic) => {
+                Some(stemmer.stem(&word).into_owned())
+            }
+            _ => Some(word),
+        }
+    }
+}
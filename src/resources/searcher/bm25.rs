@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// BM25 term frequency saturation
+const K1: f32 = 1.2;
+/// BM25 document length normalisation
+const B: f32 = 0.75;
+
+/// The fields of a resource that are indexed for full-text search
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum Field {
+    Title,
+    Description,
+    Tags,
+    Content,
+}
+
+impl Field {
+    /// How much an occurrence in the field counts compared to one in the content
+    pub fn boost(&self) -> f32 {
+        match self {
+            Field::Title => 3.0,
+            Field::Tags => 2.0,
+            Field::Description => 1.5,
+            Field::Content => 1.0,
+        }
+    }
+}
+
+/// The weighted frequency of a term in a document and the positions it appears at
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Posting {
+    doc: u32,
+    tf: f32,
+    positions: Vec<u32>,
+}
+
+/// # Full-Text Index
+///
+/// An inverted index from terms to the documents containing them, ranked with BM25.
+/// Fields are combined before ranking (BM25F), an occurrence in a field adds its
+/// [`Field::boost`] to both the term frequency and the document length.
+///
+/// The positions of the terms are kept for phrase matching, with the fields of a document
+/// numbered one after another and a gap between them so phrases can't cross fields.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TextIndex {
+    postings: HashMap<String, Vec<Posting>>,
+    /// The weighted length of every document, indexed by document id
+    lengths: Vec<f32>,
+    total_length: f64,
+    /// The terms of every document, so it can be removed without scanning every posting list
+    terms: Vec<Vec<String>>,
+    /// The number of documents that haven't been removed
+    live: usize,
+}
+
+impl TextIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the analyzed terms of the fields of the next document,
+    /// documents are given ids in the order they're added
+    pub fn add(&mut self, fields: &[(Field, Vec<String>)]) -> u32 {
+        let doc = self.lengths.len() as u32;
+        let mut postings: HashMap<String, Posting> = HashMap::new();
+        let mut length = 0.0;
+        let mut position = 0;
+        for (field, terms) in fields {
+            for term in terms {
+                let posting = postings.entry(term.clone()).or_insert_with(|| Posting {
+                    doc,
+                    tf: 0.0,
+                    positions: Vec::new(),
+                });
+                posting.tf += field.boost();
+                posting.positions.push(position);
+                length += field.boost();
+                position += 1;
+            }
+            position += 1;
+        }
+        let mut terms = Vec::with_capacity(postings.len());
+        for (term, posting) in postings {
+            self.postings.entry(term.clone()).or_default().push(posting);
+            terms.push(term);
+        }
+        self.lengths.push(length);
+        self.total_length += length as f64;
+        self.terms.push(terms);
+        self.live += 1;
+        doc
+    }
+
+    /// Removes a document, its id isn't reused so the ids of the other documents don't change
+    pub fn remove(&mut self, doc: u32) {
+        let terms = match self.terms.get_mut(doc as usize) {
+            Some(terms) if self.lengths[doc as usize] >= 0.0 => std::mem::take(terms),
+            _ => return,
+        };
+        for term in terms {
+            if let Some(postings) = self.postings.get_mut(&term) {
+                postings.retain(|x| x.doc != doc);
+                if postings.is_empty() {
+                    self.postings.remove(&term);
+                }
+            }
+        }
+        self.total_length -= self.lengths[doc as usize] as f64;
+        // a negative length marks the document as removed
+        self.lengths[doc as usize] = -1.0;
+        self.live -= 1;
+    }
+
+    /// The number of documents in the index
+    pub fn len(&self) -> usize {
+        self.live
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.live == 0
+    }
+
+    /// The documents containing the term, in increasing order
+    pub fn docs_with(&self, term: &str) -> Vec<u32> {
+        self.postings
+            .get(term)
+            .map(|postings| postings.iter().map(|x| x.doc).collect())
+            .unwrap_or_default()
+    }
+
+    /// The documents containing the terms next to each other in order, in increasing order
+    pub fn docs_with_phrase(&self, terms: &[String]) -> Vec<u32> {
+        let lists: Option<Vec<HashMap<u32, &Vec<u32>>>> = terms
+            .iter()
+            .map(|term| {
+                self.postings
+                    .get(term)
+                    .map(|postings| postings.iter().map(|x| (x.doc, &x.positions)).collect())
+            })
+            .collect();
+        let lists = match lists {
+            Some(lists) if !lists.is_empty() => lists,
+            _ => return Vec::new(),
+        };
+
+        let mut docs: Vec<u32> = lists[0]
+            .iter()
+            .filter(|(doc, starts)| {
+                starts.iter().any(|start| {
+                    lists[1..].iter().enumerate().all(|(offset, list)| {
+                        list.get(doc)
+                            .is_some_and(|p| p.contains(&(start + offset as u32 + 1)))
+                    })
+                })
+            })
+            .map(|(doc, _)| *doc)
+            .collect();
+        docs.sort_unstable();
+        docs
+    }
+
+    /// The BM25 score of every document containing at least one of the terms
+    pub fn scores(&self, terms: &[String]) -> HashMap<u32, f32> {
+        let mut scores: HashMap<u32, f32> = HashMap::new();
+        let n = self.live as f32;
+        if n == 0.0 {
+            return scores;
+        }
+        let avg_length = (self.total_length / n as f64) as f32;
+
+        let mut terms = terms.to_vec();
+        terms.sort();
+        terms.dedup();
+        for term in &terms {
+            let postings = match self.postings.get(term) {
+                Some(postings) => postings,
+                None => continue,
+            };
+            let df = postings.len() as f32;
+            let idf = (1.0 + (n - df + 0.5) / (df + 0.5)).ln();
+            for posting in postings {
+                let length = self.lengths[posting.doc as usize];
+                let norm = K1 * (1.0 - B + B * length / avg_length.max(f32::EPSILON));
+                *scores.entry(posting.doc).or_default() +=
+                    idf * posting.tf * (K1 + 1.0) / (posting.tf + norm);
+            }
+        }
+        scores
+    }
+
+    /// Scores the documents matching any of the terms, best match first
+    pub fn search(&self, terms: &[String]) -> Vec<(u32, f32)> {
+        let mut scores: Vec<(u32, f32)> = self.scores(terms).into_iter().collect();
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        scores
+    }
+
+    /// Encodes the posting lists of every term, sorted by term, for the export format.
+    ///
+    /// The documents are renumbered with `ids` (removed documents have no id), then every list is
+    /// a sequence of varints: the number of postings, then for every posting the gap from the
+    /// previous document, twice the term frequency (the boosts are multiples of a half),
+    /// the number of positions and the gaps between the positions.
+    pub fn encode(&self, ids: &[Option<u32>]) -> Vec<(String, Vec<u8>)> {
+        let mut terms: Vec<(String, Vec<u8>)> = self
+            .postings
+            .iter()
+            .map(|(term, postings)| {
+                let mut postings: Vec<(u32, &Posting)> = postings
+                    .iter()
+                    .filter_map(|x| Some((ids[x.doc as usize]?, x)))
+                    .collect();
+                postings.sort_by_key(|(doc, _)| *doc);
+
+                let mut bytes = Vec::new();
+                write_varint(&mut bytes, postings.len() as u32);
+                let mut previous = 0;
+                for (doc, posting) in postings {
+                    write_varint(&mut bytes, doc - previous);
+                    write_varint(&mut bytes, (posting.tf * 2.0).round() as u32);
+                    write_varint(&mut bytes, posting.positions.len() as u32);
+                    let mut last = 0;
+                    for position in &posting.positions {
+                        write_varint(&mut bytes, position - last);
+                        last = *position;
+                    }
+                    previous = doc;
+                }
+                (term.clone(), bytes)
+            })
+            .collect();
+        terms.sort();
+        terms
+    }
+
+    /// Rebuilds the index of `docs` documents from the posting lists made by [`TextIndex::encode`],
+    /// or `None` if they're malformed
+    pub fn decode(terms: &[(String, Vec<u8>)], docs: usize) -> Option<Self> {
+        let mut index = Self {
+            lengths: vec![0.0; docs],
+            terms: vec![Vec::new(); docs],
+            live: docs,
+            ..Self::default()
+        };
+        for (term, bytes) in terms {
+            let mut bytes = bytes.iter().copied();
+            let count = read_varint(&mut bytes)?;
+            // every posting takes at least 3 bytes, so a hostile count can't allocate more than the input
+            let mut postings = Vec::with_capacity((count as usize).min(bytes.len() / 3));
+            let mut doc = 0u32;
+            for _ in 0..count {
+                doc = doc.checked_add(read_varint(&mut bytes)?)?;
+                let tf = read_varint(&mut bytes)? as f32 / 2.0;
+                let mut positions = Vec::new();
+                let mut position = 0u32;
+                for _ in 0..read_varint(&mut bytes)? {
+                    position = position.checked_add(read_varint(&mut bytes)?)?;
+                    positions.push(position);
+                }
+                *index.lengths.get_mut(doc as usize)? += tf;
+                index.terms[doc as usize].push(term.clone());
+                postings.push(Posting { doc, tf, positions });
+            }
+            if bytes.next().is_some() {
+                return None;
+            }
+            index.postings.insert(term.clone(), postings);
+        }
+        index.total_length = index.lengths.iter().map(|x| *x as f64).sum();
+        Some(index)
+    }
+}
+
+/// Appends a LEB128 varint, 7 bits per byte with the high bit set on every byte but the last
+fn write_varint(bytes: &mut Vec<u8>, mut value: u32) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Reads a varint written by [`write_varint`], `None` if the input ends or the value overflows a `u32`
+fn read_varint(bytes: &mut impl Iterator<Item = u8>) -> Option<u32> {
+    let mut value = 0u64;
+    for shift in (0..35).step_by(7) {
+        let byte = bytes.next()?;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return u32::try_from(value).ok();
+        }
+    }
+    None
+}
@@ -0,0 +1,159 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use super::analyzer::Analyzer;
+use super::bm25::TextIndex;
+use super::{Document, Searcher};
+
+/// The version of the export format, bumped whenever its layout changes
+pub const FORMAT_VERSION: u32 = 3;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// # Search Index
+///
+/// The compact export format of a [`Searcher`], for shipping the index to browsers and offline apps.
+///
+/// Every document is stored once, identified by its position, and the hashmaps and suggestions
+/// are rebuilt from them when loading. The posting lists are delta and varint encoded
+/// (see [`TextIndex::encode`]) and stored as base64 so they stay small in JSON.
+///
+/// The plain text of the descriptions and contents is only needed for snippets, so it's left
+/// out unless exported with [`Searcher::export_with_text`]; results of an index without it have
+/// no snippets.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SearchIndex {
+    pub version: u32,
+    /// The analyzer the terms were made with, which the queries must be analyzed with too
+    analyzer: Analyzer,
+    docs: Vec<Document>,
+    /// The encoded posting lists by term
+    postings: Vec<(String, String)>,
+}
+
+/// Why an exported index couldn't be loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexError {
+    /// The index was exported in a format this version can't read
+    UnsupportedVersion(u32),
+    Malformed(String),
+}
+
+impl Display for IndexError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IndexError::UnsupportedVersion(version) => write!(
+                f,
+                "index format version {version} is not supported, expected {FORMAT_VERSION}"
+            ),
+            IndexError::Malformed(reason) => write!(f, "malformed index: {reason}"),
+        }
+    }
+}
+
+impl Error for IndexError {}
+
+impl Searcher {
+    /// Exports the searcher to the compact format, leaving out removed resources
+    /// and the text of the documents
+    pub fn export(&self) -> SearchIndex {
+        let mut index = self.export_with_text();
+        for doc in &mut index.docs {
+            doc.description.clear();
+            doc.content.clear();
+        }
+        index
+    }
+
+    /// Exports the searcher to the compact format like [`Searcher::export`],
+    /// keeping the text of the documents for snippets
+    pub fn export_with_text(&self) -> SearchIndex {
+        let ids = self.live_ids();
+        SearchIndex {
+            version: FORMAT_VERSION,
+            analyzer: self.analyzer.clone(),
+            docs: self.docs.iter().flatten().cloned().collect(),
+            postings: self
+                .text
+                .encode(&ids)
+                .into_iter()
+                .map(|(term, bytes)| (term, encode_base64(&bytes)))
+                .collect(),
+        }
+    }
+
+    /// Loads an exported index without tokenizing the resources again
+    pub fn import(index: SearchIndex) -> Result<Self, IndexError> {
+        if index.version != FORMAT_VERSION {
+            return Err(IndexError::UnsupportedVersion(index.version));
+        }
+        let postings = index
+            .postings
+            .into_iter()
+            .map(|(term, text)| match decode_base64(&text) {
+                Some(bytes) => Ok((term, bytes)),
+                None => Err(IndexError::Malformed(format!("postings of `{term}`"))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let text = TextIndex::decode(&postings, index.docs.len())
+            .ok_or_else(|| IndexError::Malformed("postings".to_string()))?;
+        Ok(Self::from_parts(index.analyzer, text, index.docs))
+    }
+
+    /// Exports the searcher as JSON, see [`SearchIndex`]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(&self.export())
+    }
+
+    /// Loads an index exported with [`Searcher::to_json`], checking the format version first
+    pub fn from_json(json: &str) -> Result<Self, IndexError> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| IndexError::Malformed(e.to_string()))?;
+        match value.get("version").and_then(|x| x.as_u64()) {
+            Some(version) if version == FORMAT_VERSION as u64 => {}
+            Some(version) => return Err(IndexError::UnsupportedVersion(version as u32)),
+            None => return Err(IndexError::Malformed("missing version".to_string())),
+        }
+        let index: SearchIndex =
+            serde_json::from_value(value).map_err(|e| IndexError::Malformed(e.to_string()))?;
+        Self::import(index)
+    }
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, byte)| n | ((*byte as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(BASE64[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim_end_matches('=');
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let mut n = 0u32;
+    let mut bits = 0;
+    for c in text.bytes() {
+        n = (n << 6) | BASE64.iter().position(|x| *x == c)? as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((n >> bits) as u8);
+            n &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
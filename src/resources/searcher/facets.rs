@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::query::Filter;
+use super::text::normalize;
+use super::Document;
+
+/// A value of a facet with the number of results having it
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FacetValue {
+    pub label: String,
+    pub count: usize,
+    /// The filter that drills down to the results with this value
+    pub filter: Filter,
+}
+
+/// # Facets
+///
+/// The counts of the languages, resource types, levels, tags and authors of a set of
+/// search results, most common first, for filter sidebars like `Rust (12), Python (4)`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Facets {
+    pub langs: Vec<FacetValue>,
+    pub types: Vec<FacetValue>,
+    pub levels: Vec<FacetValue>,
+    pub tags: Vec<FacetValue>,
+    pub authors: Vec<FacetValue>,
+}
+
+/// Counts the values of a facet, remembering the label and filter of the first occurrence
+#[derive(Default)]
+struct Counter(HashMap<String, (String, usize, Filter)>);
+
+impl Counter {
+    fn add(&mut self, key: &str, label: &str, filter: Filter) {
+        let entry = self.0.entry(key.to_string());
+        entry.or_insert_with(|| (label.to_string(), 0, filter)).1 += 1;
+    }
+
+    fn values(self) -> Vec<FacetValue> {
+        let mut values: Vec<FacetValue> = self
+            .0
+            .into_values()
+            .map(|(label, count, filter)| FacetValue {
+                label,
+                count,
+                filter,
+            })
+            .collect();
+        values.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.label.cmp(&b.label)));
+        values
+    }
+}
+
+impl Facets {
+    pub(super) fn count<'a>(docs: impl IntoIterator<Item = &'a Document>) -> Self {
+        let mut langs = Counter::default();
+        let mut types = Counter::default();
+        let mut levels = Counter::default();
+        let mut tags = Counter::default();
+        let mut authors = Counter::default();
+
+        for doc in docs {
+            for lang in &doc.langs {
+                let label = lang.to_string();
+                langs.add(&label, &label, Filter::Lang(*lang));
+            }
+            let ty = format!("{:?}", doc.meta.ty);
+            types.add(&ty, &ty, Filter::Type(doc.meta.ty));
+            if let Some(level) = doc.level {
+                let label = format!("{level:?}");
+                levels.add(&label, &label, Filter::Level(level));
+            }
+            for tag in &doc.tags {
+                let key = normalize(tag);
+                if !key.is_empty() {
+                    tags.add(&key, &key, Filter::Tag(key.clone()));
+                }
+            }
+            let author = doc.author.trim();
+            if !author.is_empty() {
+                let key = normalize(author);
+                authors.add(&key, author, Filter::Author(key.clone()));
+            }
+        }
+
+        Self {
+            langs: langs.values(),
+            types: types.values(),
+            levels: levels.values(),
+            tags: tags.values(),
+            authors: authors.values(),
+        }
+    }
+}
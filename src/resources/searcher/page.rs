@@ -0,0 +1,136 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::facets::Facets;
+use super::query::Filter;
+use super::SearchHit;
+
+/// The number of hits in a page when the request doesn't set a limit
+pub const DEFAULT_LIMIT: usize = 20;
+
+/// How the hits of a [`SearchRequest`] are ordered
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SortBy {
+    /// Best match first
+    #[default]
+    Relevance,
+    /// Newest first, resources without a date last
+    Date,
+    /// Alphabetically, ignoring case
+    Title,
+    /// Beginner first, resources without a level last
+    Level,
+}
+
+impl FromStr for SortBy {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "relevance" => Ok(SortBy::Relevance),
+            "date" => Ok(SortBy::Date),
+            "title" => Ok(SortBy::Title),
+            "level" => Ok(SortBy::Level),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Where the previous page ended, so the next page continues after the same resource
+/// even if resources were inserted or removed in between
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Cursor {
+    /// The position after the previous page, used if its last resource was removed
+    pub(super) offset: usize,
+    /// The key of the last resource of the previous page
+    pub(super) key: String,
+}
+
+impl Display for Cursor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.offset, self.key)
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (offset, key) = s.split_once(':').ok_or(())?;
+        Ok(Self {
+            offset: offset.parse().map_err(|_| ())?,
+            key: key.to_string(),
+        })
+    }
+}
+
+/// # Search Request
+///
+/// A structured query (see [`Searcher::query`](super::Searcher::query)) with facet filters,
+/// a sort order and the page to return, either by offset or by the cursor of the previous page.
+///
+/// ```rust
+/// use mokareads_core::resources::searcher::page::{SearchRequest, SortBy};
+///
+/// let request = SearchRequest::new("lang:rust ownership")
+///     .with_sort(SortBy::Level)
+///     .with_limit(10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SearchRequest {
+    pub(super) query: String,
+    pub(super) filters: Vec<Filter>,
+    pub(super) sort: SortBy,
+    pub(super) offset: usize,
+    pub(super) limit: usize,
+    pub(super) cursor: Option<Cursor>,
+}
+
+impl SearchRequest {
+    /// A request for the first page of the query, an empty query matches every resource
+    pub fn new(query: &str) -> Self {
+        Self {
+            query: query.to_string(),
+            filters: Vec::new(),
+            sort: SortBy::default(),
+            offset: 0,
+            limit: DEFAULT_LIMIT,
+            cursor: None,
+        }
+    }
+    pub fn with_filters(mut self, filters: &[Filter]) -> Self {
+        self.filters = filters.to_vec();
+        self
+    }
+    pub fn with_sort(mut self, sort: SortBy) -> Self {
+        self.sort = sort;
+        self
+    }
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+    /// Continues after the page the cursor came from, taking precedence over the offset
+    pub fn with_cursor(mut self, cursor: Cursor) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+}
+
+/// A page of the results of a [`SearchRequest`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SearchPage {
+    pub hits: Vec<SearchHit>,
+    /// The number of resources matching the request across every page
+    pub total: usize,
+    /// The position of the first hit of the page in the results
+    pub offset: usize,
+    /// The cursor of the next page, `None` on the last page
+    pub next: Option<Cursor>,
+    /// The facets of every matching resource, not only the ones on the page
+    pub facets: Facets,
+}
@@ -0,0 +1,128 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+use super::Searcher;
+
+/// The first bytes of every saved searcher
+const MAGIC: &[u8; 8] = b"MOKAIDX\0";
+/// The version of the binary format, bumped whenever the layout of the [`Searcher`] changes
+pub const BINARY_VERSION: u32 = 1;
+/// The magic, version, payload length and checksum
+const HEADER_LEN: usize = 8 + 4 + 8 + 4;
+
+/// Why a searcher couldn't be saved or loaded
+#[derive(Debug)]
+pub enum PersistError {
+    Io(std::io::Error),
+    /// The file doesn't start with the magic bytes
+    NotAnIndex,
+    /// The file was saved in a format this version can't read
+    UnsupportedVersion(u32),
+    /// The file is shorter than its header says
+    Truncated,
+    /// The payload doesn't match its checksum
+    Checksum {
+        expected: u32,
+        found: u32,
+    },
+    Encode(String),
+    Decode(String),
+}
+
+impl Display for PersistError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PersistError::Io(e) => write!(f, "{e}"),
+            PersistError::NotAnIndex => write!(f, "not a saved searcher"),
+            PersistError::UnsupportedVersion(version) => write!(
+                f,
+                "searcher format version {version} is not supported, expected {BINARY_VERSION}"
+            ),
+            PersistError::Truncated => write!(f, "the saved searcher is truncated"),
+            PersistError::Checksum { expected, found } => write!(
+                f,
+                "checksum mismatch, expected {expected:08x} but found {found:08x}"
+            ),
+            PersistError::Encode(e) => write!(f, "couldn't encode the searcher: {e}"),
+            PersistError::Decode(e) => write!(f, "couldn't decode the searcher: {e}"),
+        }
+    }
+}
+
+impl Error for PersistError {}
+
+impl From<std::io::Error> for PersistError {
+    fn from(value: std::io::Error) -> Self {
+        PersistError::Io(value)
+    }
+}
+
+impl Searcher {
+    /// Encodes the searcher with every index already built, so loading it doesn't redo any work.
+    ///
+    /// The layout is the magic bytes `MOKAIDX\0`, then the format version (`u32`), the length
+    /// of the payload (`u64`) and its CRC-32 (`u32`) in little endian, then the payload, which is
+    /// the searcher in MessagePack. The observer isn't saved.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, PersistError> {
+        let payload =
+            rmp_serde::to_vec_named(self).map_err(|e| PersistError::Encode(e.to_string()))?;
+        let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&BINARY_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&crc32fast::hash(&payload).to_le_bytes());
+        bytes.extend_from_slice(&payload);
+        Ok(bytes)
+    }
+
+    /// Decodes a searcher encoded with [`Searcher::to_bytes`], checking its version and checksum
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PersistError> {
+        if bytes.len() < HEADER_LEN || &bytes[..8] != MAGIC {
+            return Err(PersistError::NotAnIndex);
+        }
+        let version = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        if version != BINARY_VERSION {
+            return Err(PersistError::UnsupportedVersion(version));
+        }
+        let len = u64::from_le_bytes(bytes[12..20].try_into().unwrap());
+        let expected = u32::from_le_bytes(bytes[20..24].try_into().unwrap());
+        let payload = usize::try_from(len)
+            .ok()
+            .and_then(|len| bytes.get(HEADER_LEN..HEADER_LEN.checked_add(len)?))
+            .ok_or(PersistError::Truncated)?;
+        let found = crc32fast::hash(payload);
+        if found != expected {
+            return Err(PersistError::Checksum { expected, found });
+        }
+        rmp_serde::from_slice(payload).map_err(|e| PersistError::Decode(e.to_string()))
+    }
+
+    /// Saves the searcher to a file, writing a temporary file first so a crash
+    /// never leaves a partly written searcher behind
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), PersistError> {
+        let path = path.as_ref();
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        fs::write(&temp, self.to_bytes()?)?;
+        fs::rename(&temp, path)?;
+        Ok(())
+    }
+
+    /// Loads a searcher saved with [`Searcher::save`]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PersistError> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    /// Loads a searcher saved with [`Searcher::save`] by memory mapping the file,
+    /// which avoids copying it into memory before decoding
+    #[cfg(feature = "mmap")]
+    pub fn load_mmap(path: impl AsRef<Path>) -> Result<Self, PersistError> {
+        let file = fs::File::open(path)?;
+        // Safety: the map is only read while decoding, `save` replaces files by renaming
+        // rather than writing to them in place
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Self::from_bytes(&map)
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::text::normalize;
+use super::{Document, SearchMetadata, Searcher};
+use crate::resources::cheatsheet::Language;
+use crate::resources::ResourceType;
+
+/// # Author Profile
+///
+/// Everything an author wrote, for `/authors/{slug}` pages.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AuthorProfile {
+    /// The name as written on the author's first indexed resource
+    pub name: String,
+    /// The normalised name the profile is found by, e.g. `jane_doe`
+    pub slug: String,
+    /// Newest first
+    pub articles: Vec<SearchMetadata>,
+    /// By language, then from beginner to advanced
+    pub cheatsheets: Vec<SearchMetadata>,
+    /// The normalised tags of the author's resources, most used first
+    pub tags: Vec<(String, usize)>,
+    /// The languages the author's resources are about
+    pub langs: Vec<Language>,
+}
+
+/// Sorts counts from the most common, alphabetically when equal
+fn by_count(counts: HashMap<String, usize>) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+impl Searcher {
+    /// The resources with the tag, for `/tags/{tag}` pages. The tag is normalised first,
+    /// so `Machine Learning` and `machine_learning` find the same resources.
+    pub fn tag(&self, tag: &str) -> Vec<SearchMetadata> {
+        self.tags
+            .get(&normalize(tag))
+            .map(|docs| self.metas(docs))
+            .unwrap_or_default()
+    }
+
+    /// Every normalised tag with the number of resources having it, most used first
+    pub fn tags(&self) -> Vec<(String, usize)> {
+        by_count(
+            self.tags
+                .iter()
+                .map(|(tag, docs)| (tag.clone(), docs.len()))
+                .collect(),
+        )
+    }
+
+    /// The profile of an author, found by their name or slug ignoring case and spacing
+    pub fn author(&self, name: &str) -> Option<AuthorProfile> {
+        let slug = normalize(name);
+        let docs: Vec<&Document> = self
+            .authors
+            .get(&slug)?
+            .iter()
+            .filter_map(|doc| self.docs[*doc as usize].as_ref())
+            .collect();
+
+        let of_type = |ty: ResourceType| docs.iter().filter(move |x| x.meta.ty == ty);
+        let mut articles: Vec<&&Document> = of_type(ResourceType::Article).collect();
+        articles.sort_by(|a, b| b.date.cmp(&a.date));
+        let mut cheatsheets: Vec<&&Document> = of_type(ResourceType::Cheatsheet).collect();
+        cheatsheets.sort_by_key(|x| (x.langs.first().copied(), x.level));
+
+        let mut tags = HashMap::new();
+        for tag in docs.iter().flat_map(|x| x.tag_keys()) {
+            *tags.entry(tag).or_default() += 1;
+        }
+        let mut langs: Vec<Language> = docs.iter().flat_map(|x| x.langs.clone()).collect();
+        langs.sort();
+        langs.dedup();
+
+        Some(AuthorProfile {
+            name: docs.first()?.author.trim().to_string(),
+            slug,
+            articles: articles.iter().map(|x| x.meta.clone()).collect(),
+            cheatsheets: cheatsheets.iter().map(|x| x.meta.clone()).collect(),
+            tags: by_count(tags),
+            langs,
+        })
+    }
+
+    /// The slugs of every author with the number of resources they wrote, most prolific first
+    pub fn authors(&self) -> Vec<(String, usize)> {
+        by_count(
+            self.authors
+                .iter()
+                .map(|(author, docs)| (author.clone(), docs.len()))
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,333 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::resources::cheatsheet::{Language, Level};
+use crate::resources::ResourceType;
+
+use super::analyzer::SYNONYMS;
+use super::text::normalize;
+
+/// A filter on a field of a resource, written as `field:value`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Filter {
+    /// `lang:rust`, or a synonym of the language like `lang:rs` (see [`SYNONYMS`])
+    Lang(Language),
+    /// `type:cheatsheet`
+    Type(ResourceType),
+    /// `level:beginner` or `level:1`
+    Level(Level),
+    /// `author:"Jane Doe"`, matches an author equal to the value once both are normalised like
+    /// the author index (see [`normalize`])
+    Author(String),
+    /// `tag:async`, matches a tag equal to the value once both are normalised (see [`normalize`])
+    Tag(String),
+    /// `title:ownership`, matches titles containing the value, ignoring case
+    Title(String),
+}
+
+/// # Query
+///
+/// The syntax tree of a structured search query such as
+/// `lang:rust type:cheatsheet -unsafe "smart pointers" OR author:"Jane Doe" tag:async`.
+///
+/// - Words are full-text terms, and quoted text is a phrase whose terms must appear in order
+/// - `field:value` filters on `lang`, `type`, `level`, `author`, `tag` or `title`, any other
+///   `word:` is full text so `Rust: ownership`, `std::vec` and links can be searched
+/// - Whitespace separated parts must all match, `OR` (which binds looser) needs either side to match
+/// - `-` or `NOT` negates the part after it and parentheses group parts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    Term(String),
+    Phrase(String),
+    Filter(Filter),
+    Not(Box<Query>),
+    And(Vec<Query>),
+    Or(Vec<Query>),
+}
+
+/// Why a query couldn't be parsed, with the byte offset in the input it happened at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    pub message: String,
+    pub position: usize,
+}
+
+impl QueryError {
+    fn new(message: impl Into<String>, position: usize) -> Self {
+        Self {
+            message: message.into(),
+            position,
+        }
+    }
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at position {})", self.message, self.position)
+    }
+}
+
+impl Error for QueryError {}
+
+/// The fields a `field:value` filter can be on
+const FIELDS: [&str; 7] = [
+    "lang", "language", "type", "level", "author", "tag", "title",
+];
+
+/// How deeply parentheses and negations may nest, so a hostile query can't overflow the stack
+const MAX_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    /// A `field:` prefix, the value is the next token
+    Field(String),
+    Minus,
+    Not,
+    Or,
+    Open,
+    Close,
+}
+
+fn lex(input: &str) -> Result<Vec<(Token, usize)>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' => {
+                chars.next();
+                let token = if c == '(' { Token::Open } else { Token::Close };
+                tokens.push((token, start));
+            }
+            '-' => {
+                chars.next();
+                tokens.push((Token::Minus, start));
+            }
+            '"' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, c)) => text.push(c),
+                        None => return Err(QueryError::new("unterminated quote", start)),
+                    }
+                }
+                tokens.push((Token::Quoted(text), start));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if c.is_whitespace() || matches!(c, '(' | ')' | '"') {
+                        break;
+                    }
+                    chars.next();
+                    word.push(c);
+                }
+                let field = word
+                    .split_once(':')
+                    .filter(|(field, _)| FIELDS.contains(&field.to_lowercase().as_str()));
+                match field {
+                    Some((field, value)) => {
+                        tokens.push((Token::Field(field.to_lowercase()), start));
+                        if !value.is_empty() {
+                            tokens.push((Token::Word(value.to_string()), start + field.len() + 1));
+                        }
+                    }
+                    None if word == "OR" => tokens.push((Token::Or, start)),
+                    None if word == "NOT" => tokens.push((Token::Not, start)),
+                    None => tokens.push((Token::Word(word), start)),
+                }
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct QueryParser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    len: usize,
+    /// The parentheses and negations the parser is nested in
+    depth: usize,
+}
+
+impl QueryParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map_or(self.len, |(_, offset)| *offset)
+    }
+    /// Parses the part of the query nested by the token just taken,
+    /// failing at that token once it's nested past [`MAX_DEPTH`]
+    fn nested(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<Query, QueryError>,
+    ) -> Result<Query, QueryError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(QueryError::new(
+                "the query is nested too deeply",
+                self.tokens[self.pos - 1].1,
+            ));
+        }
+        self.depth += 1;
+        let query = parse(self);
+        self.depth -= 1;
+        query
+    }
+
+    fn or(&mut self) -> Result<Query, QueryError> {
+        let mut parts = vec![self.and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            parts.push(self.and()?);
+        }
+        Ok(if parts.len() == 1 {
+            parts.remove(0)
+        } else {
+            Query::Or(parts)
+        })
+    }
+
+    fn and(&mut self) -> Result<Query, QueryError> {
+        let mut parts = Vec::new();
+        while !matches!(self.peek(), None | Some(Token::Or) | Some(Token::Close)) {
+            parts.push(self.unary()?);
+        }
+        match parts.len() {
+            0 => Err(QueryError::new("expected a search term", self.offset())),
+            1 => Ok(parts.remove(0)),
+            _ => Ok(Query::And(parts)),
+        }
+    }
+
+    fn unary(&mut self) -> Result<Query, QueryError> {
+        match self.peek() {
+            Some(Token::Minus) | Some(Token::Not) => {
+                self.pos += 1;
+                if matches!(self.peek(), None | Some(Token::Or) | Some(Token::Close)) {
+                    return Err(QueryError::new("nothing to negate", self.offset()));
+                }
+                Ok(Query::Not(Box::new(self.nested(Self::unary)?)))
+            }
+            _ => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Query, QueryError> {
+        let offset = self.offset();
+        let token = self.tokens[self.pos].0.clone();
+        self.pos += 1;
+        match token {
+            Token::Word(word) => Ok(Query::Term(word)),
+            Token::Quoted(text) => Ok(Query::Phrase(text)),
+            Token::Open => {
+                let query = self.nested(Self::or)?;
+                if self.peek() != Some(&Token::Close) {
+                    return Err(QueryError::new("expected `)`", self.offset()));
+                }
+                self.pos += 1;
+                Ok(query)
+            }
+            Token::Field(field) => {
+                let value = match self.tokens.get(self.pos) {
+                    Some((Token::Word(value), _)) | Some((Token::Quoted(value), _)) => {
+                        value.clone()
+                    }
+                    _ => {
+                        return Err(QueryError::new(
+                            format!("expected a value for `{field}:`"),
+                            self.offset(),
+                        ))
+                    }
+                };
+                self.pos += 1;
+                filter(&field, &value)
+                    .map(Query::Filter)
+                    .map_err(|message| QueryError::new(message, offset))
+            }
+            Token::Close => Err(QueryError::new("unexpected `)`", offset)),
+            Token::Or | Token::Not | Token::Minus => {
+                Err(QueryError::new("expected a search term", offset))
+            }
+        }
+    }
+}
+
+fn filter(field: &str, value: &str) -> Result<Filter, String> {
+    let lower = value.to_lowercase();
+    match field {
+        "lang" | "language" => {
+            let name = SYNONYMS
+                .iter()
+                .find(|(from, _)| *from == lower)
+                .map_or(lower.as_str(), |(_, to)| to);
+            match Language::from_str(name) {
+                // languages without a variant, like javascript, are only found by their tags
+                Language::Other if SYNONYMS.iter().any(|(_, to)| *to == name) => {
+                    Ok(Filter::Tag(normalize(name)))
+                }
+                Language::Other => Err(format!("unknown language `{value}`")),
+                lang => Ok(Filter::Lang(lang)),
+            }
+        }
+        "type" => ResourceType::from_str(&lower)
+            .map(Filter::Type)
+            .map_err(|_| format!("unknown resource type `{value}`")),
+        "level" => {
+            let level = match lower.as_str() {
+                "beginner" => Some(Level::Beginner),
+                "intermediate" => Some(Level::Intermediate),
+                "advanced" => Some(Level::Advanced),
+                other => other.parse().ok().and_then(Level::from_u8),
+            };
+            level
+                .map(Filter::Level)
+                .ok_or_else(|| format!("unknown level `{value}`"))
+        }
+        "author" => Ok(Filter::Author(normalize(value))),
+        "tag" => Ok(Filter::Tag(normalize(value))),
+        "title" => Ok(Filter::Title(lower)),
+        _ => Err(format!("unknown field `{field}`")),
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = QueryParser {
+            tokens: lex(s)?,
+            pos: 0,
+            len: s.len(),
+            depth: 0,
+        };
+        let query = parser.or()?;
+        match parser.peek() {
+            None => Ok(query),
+            Some(_) => Err(QueryError::new("unexpected `)`", parser.offset())),
+        }
+    }
+}
+
+impl Query {
+    /// The full-text terms and phrases that aren't negated, used to rank the matches
+    pub fn positive_text(&self) -> Vec<String> {
+        match self {
+            Query::Term(text) | Query::Phrase(text) => vec![text.clone()],
+            Query::Filter(_) | Query::Not(_) => Vec::new(),
+            Query::And(parts) | Query::Or(parts) => {
+                parts.iter().flat_map(|x| x.positive_text()).collect()
+            }
+        }
+    }
+}
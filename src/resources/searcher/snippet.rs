@@ -0,0 +1,156 @@
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use super::analyzer::Analyzer;
+use super::text::tokens;
+
+/// The number of terms shown in a snippet
+const SNIPPET_TERMS: usize = 30;
+/// The number of terms shown before the first match in a snippet
+const LEADING_TERMS: usize = 6;
+
+/// # Snippet
+///
+/// An excerpt of the plain text of a resource around the best match of a search,
+/// so a results page can show why the resource matched.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Snippet {
+    /// The excerpt with its whitespace collapsed and `…` where it was cut
+    pub text: String,
+    /// The byte ranges of the matched terms in `text`
+    pub highlights: Vec<Range<usize>>,
+    /// `text` escaped for html with the matched terms wrapped in `<mark>`
+    pub html: String,
+}
+
+impl Snippet {
+    /// The excerpt of the text with the most distinct terms of the search (then the most matches),
+    /// or `None` if none of the terms appear in the text. The terms must come from the analyzer
+    /// the text is analyzed with.
+    pub fn around(text: &str, terms: &[String], analyzer: &Analyzer) -> Option<Self> {
+        let tokens = analyzer.tokens(text);
+        let matched: Vec<bool> = tokens.iter().map(|(_, t)| terms.contains(t)).collect();
+
+        let mut best: Option<((usize, usize), usize)> = None;
+        for (i, _) in matched.iter().enumerate().filter(|(_, m)| **m) {
+            let start = i.saturating_sub(LEADING_TERMS);
+            let end = (start + SNIPPET_TERMS).min(tokens.len());
+            let mut distinct: Vec<&str> = (start..end)
+                .filter(|j| matched[*j])
+                .map(|j| tokens[j].1.as_str())
+                .collect();
+            let count = distinct.len();
+            distinct.sort_unstable();
+            distinct.dedup();
+            let score = (distinct.len(), count);
+            if best.is_none_or(|(best, _)| score > best) {
+                best = Some((score, start));
+            }
+        }
+
+        let (_, start) = best?;
+        let end = (start + SNIPPET_TERMS).min(tokens.len());
+        Some(Self::excerpt(text, &tokens, &matched, start..end))
+    }
+
+    /// The start of the text without highlights, or `None` if the text has no terms
+    pub fn lead(text: &str) -> Option<Self> {
+        let tokens = tokens(text);
+        if tokens.is_empty() {
+            return None;
+        }
+        let matched = vec![false; tokens.len()];
+        let end = SNIPPET_TERMS.min(tokens.len());
+        Some(Self::excerpt(text, &tokens, &matched, 0..end))
+    }
+
+    /// Builds the snippet of a window of terms, keeping the punctuation between them
+    fn excerpt(
+        text: &str,
+        tokens: &[(Range<usize>, String)],
+        matched: &[bool],
+        window: Range<usize>,
+    ) -> Self {
+        let mut excerpt = String::new();
+        let mut highlights = Vec::new();
+        if window.start > 0 {
+            excerpt.push_str("… ");
+        }
+        let mut previous = if window.start == 0 {
+            0
+        } else {
+            tokens[window.start].0.start
+        };
+        for i in window.clone() {
+            let range = &tokens[i].0;
+            push_collapsed(&mut excerpt, &text[previous..range.start]);
+            let start = excerpt.len();
+            excerpt.push_str(&text[range.clone()]);
+            if matched[i] {
+                highlights.push(start..excerpt.len());
+            }
+            previous = range.end;
+        }
+        if window.end == tokens.len() {
+            push_collapsed(&mut excerpt, &text[previous..]);
+        }
+        let trimmed = excerpt.trim_end().len();
+        excerpt.truncate(trimmed);
+        if window.end < tokens.len() {
+            excerpt.push_str(" …");
+        }
+
+        let html = to_html(&excerpt, &highlights);
+        Self {
+            text: excerpt,
+            highlights,
+            html,
+        }
+    }
+}
+
+/// Appends the text with every run of whitespace replaced by a single space,
+/// skipping leading whitespace at the start of the excerpt
+fn push_collapsed(out: &mut String, text: &str) {
+    let mut in_space = out.is_empty() || out.ends_with(' ');
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !in_space {
+                out.push(' ');
+            }
+            in_space = true;
+        } else {
+            out.push(c);
+            in_space = false;
+        }
+    }
+}
+
+/// Escapes the text for html, wrapping the highlighted ranges in `<mark>`
+fn to_html(text: &str, highlights: &[Range<usize>]) -> String {
+    let mut html = String::with_capacity(text.len() + highlights.len() * 13);
+    let mut previous = 0;
+    for range in highlights {
+        escape(&mut html, &text[previous..range.start]);
+        html.push_str("<mark>");
+        escape(&mut html, &text[range.clone()]);
+        html.push_str("</mark>");
+        previous = range.end;
+    }
+    escape(&mut html, &text[previous..]);
+    html
+}
+
+fn escape(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+}
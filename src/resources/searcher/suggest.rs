@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// What a suggestion completes to
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Deserialize, Serialize)]
+pub enum SuggestionKind {
+    Title,
+    Tag,
+}
+
+/// A title or tag that can be suggested, with the documents it belongs to
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Entry {
+    /// The lowercase text used for matching
+    key: String,
+    /// The text shown to the user
+    text: String,
+    kind: SuggestionKind,
+    docs: Vec<u32>,
+}
+
+/// A suggestion found by the [`Suggester`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggested {
+    pub text: String,
+    pub kind: SuggestionKind,
+    /// The edit distance from the input, `0` for a prefix completion
+    pub distance: usize,
+    pub docs: Vec<u32>,
+}
+
+/// The largest edit distance allowed for a term of the given length,
+/// so short terms don't match everything
+fn max_distance(len: usize) -> usize {
+    match len {
+        0..=3 => 0,
+        4..=6 => 1,
+        _ => 2,
+    }
+}
+
+/// The optimal string alignment distance (Levenshtein with transpositions) between two strings,
+/// or `None` as soon as it's known to be more than `max`
+pub fn edit_distance(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+    let mut prev2: Vec<usize> = Vec::new();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (prev[j] + 1).min(row[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(prev2[j - 2] + 1);
+            }
+        }
+        if row.iter().min().copied().unwrap_or(0) > max {
+            return None;
+        }
+        prev2 = std::mem::replace(&mut prev, row);
+    }
+    Some(prev[b.len()]).filter(|d| *d <= max)
+}
+
+/// # Suggester
+///
+/// Prefix completion and typo tolerant matching over the titles and tags of the indexed resources,
+/// built for per-keystroke calls.
+///
+/// The entries and the words of their titles are kept sorted, so completions are found with
+/// binary search, while fuzzy matches scan the words but stop once the time budget is spent.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Suggester {
+    /// Sorted by key and kind
+    entries: Vec<Entry>,
+    /// Every word of every entry, with the key and kind of the entry it belongs to, sorted by word
+    words: Vec<(String, String, SuggestionKind)>,
+}
+
+impl Suggester {
+    /// Builds the suggester from `(kind, text, doc)` triples, merging the documents of equal texts
+    pub fn new<'a>(items: impl IntoIterator<Item = (SuggestionKind, &'a str, u32)>) -> Self {
+        let mut grouped: HashMap<(SuggestionKind, String), Entry> = HashMap::new();
+        for (kind, text, doc) in items {
+            let key = text.trim().to_lowercase();
+            if key.is_empty() {
+                continue;
+            }
+            grouped
+                .entry((kind, key.clone()))
+                .or_insert_with(|| Entry {
+                    key,
+                    text: text.trim().to_string(),
+                    kind,
+                    docs: Vec::new(),
+                })
+                .docs
+                .push(doc);
+        }
+        let mut entries: Vec<Entry> = grouped.into_values().collect();
+        entries.sort_by(|a, b| (&a.key, a.kind).cmp(&(&b.key, b.kind)));
+
+        let mut words: Vec<(String, String, SuggestionKind)> =
+            entries.iter().flat_map(Self::words_of).collect();
+        words.sort();
+        words.dedup();
+
+        Self { entries, words }
+    }
+
+    fn words_of(entry: &Entry) -> impl Iterator<Item = (String, String, SuggestionKind)> + '_ {
+        entry
+            .key
+            .split_whitespace()
+            .map(|word| (word.to_string(), entry.key.clone(), entry.kind))
+    }
+
+    /// The position of the entry with the key and kind, or where it would be inserted
+    fn find(&self, key: &str, kind: SuggestionKind) -> Result<usize, usize> {
+        self.entries
+            .binary_search_by(|x| (x.key.as_str(), x.kind).cmp(&(key, kind)))
+    }
+
+    fn index_of(&self, key: &str, kind: SuggestionKind) -> u32 {
+        self.find(key, kind).expect("every word has an entry") as u32
+    }
+
+    /// Adds a document to the entry of the text, creating the entry if it's new
+    pub fn insert(&mut self, kind: SuggestionKind, text: &str, doc: u32) {
+        let key = text.trim().to_lowercase();
+        if key.is_empty() {
+            return;
+        }
+        match self.find(&key, kind) {
+            Ok(i) => {
+                if !self.entries[i].docs.contains(&doc) {
+                    self.entries[i].docs.push(doc);
+                }
+            }
+            Err(i) => {
+                let entry = Entry {
+                    key,
+                    text: text.trim().to_string(),
+                    kind,
+                    docs: vec![doc],
+                };
+                for word in Self::words_of(&entry) {
+                    if let Err(at) = self.words.binary_search(&word) {
+                        self.words.insert(at, word);
+                    }
+                }
+                self.entries.insert(i, entry);
+            }
+        }
+    }
+
+    /// Removes a document from the entry of the text, dropping the entry once it has no documents
+    pub fn remove(&mut self, kind: SuggestionKind, text: &str, doc: u32) {
+        let key = text.trim().to_lowercase();
+        let Ok(i) = self.find(&key, kind) else {
+            return;
+        };
+        self.entries[i].docs.retain(|x| *x != doc);
+        if self.entries[i].docs.is_empty() {
+            let entry = self.entries.remove(i);
+            for word in Self::words_of(&entry) {
+                if let Ok(at) = self.words.binary_search(&word) {
+                    self.words.remove(at);
+                }
+            }
+        }
+    }
+
+    /// The range of a sorted slice whose keys start with `prefix`
+    fn prefix_range<T>(items: &[T], key: impl Fn(&T) -> &str, prefix: &str) -> (usize, usize) {
+        let start = items.partition_point(|x| key(x) < prefix);
+        let len = items[start..].partition_point(|x| key(x).starts_with(prefix));
+        (start, start + len)
+    }
+
+    /// Suggests up to `limit` titles and tags for the input, giving up on fuzzy matching after `budget`.
+    ///
+    /// Entries starting with the input come first, then entries with a word starting with the
+    /// last word of the input (that contain all the other words), then entries with a word within
+    /// a small edit distance of the last word.
+    pub fn suggest(&self, input: &str, limit: usize, budget: Duration) -> Vec<Suggested> {
+        let started = Instant::now();
+        let input = input.trim().to_lowercase();
+        let mut terms: Vec<&str> = input.split_whitespace().collect();
+        let last = match terms.pop() {
+            Some(last) => last,
+            None => return Vec::new(),
+        };
+        let contains_rest = |entry: &Entry| terms.iter().all(|t| entry.key.contains(t));
+
+        // (rank, distance) of each matching entry
+        let mut found: HashMap<u32, (u8, usize)> = HashMap::new();
+        let mut add = |entry: u32, rank: u8, distance: usize| {
+            let best = found.entry(entry).or_insert((rank, distance));
+            *best = (*best).min((rank, distance));
+        };
+
+        let (start, end) = Self::prefix_range(&self.entries, |x| &x.key, &input);
+        (start..end).for_each(|i| add(i as u32, 0, 0));
+
+        let (start, end) = Self::prefix_range(&self.words, |x| &x.0, last);
+        for (_, key, kind) in &self.words[start..end] {
+            let entry = self.index_of(key, *kind);
+            if contains_rest(&self.entries[entry as usize]) {
+                add(entry, 1, 0);
+            }
+        }
+
+        let max = max_distance(last.chars().count());
+        if max > 0 {
+            for (i, (word, key, kind)) in self.words.iter().enumerate() {
+                // checking the clock for every word would cost more than the distance
+                if i % 256 == 0 && started.elapsed() > budget {
+                    break;
+                }
+                // compare against the start of the word too, so partially typed words still match
+                let head: String = word.chars().take(last.chars().count()).collect();
+                let distance = [word.as_str(), head.as_str()]
+                    .iter()
+                    .filter_map(|w| edit_distance(last, w, max))
+                    .min();
+                if let Some(distance) = distance {
+                    let entry = self.index_of(key, *kind);
+                    if contains_rest(&self.entries[entry as usize]) {
+                        add(entry, 2, distance);
+                    }
+                }
+            }
+        }
+
+        let mut found: Vec<(u32, (u8, usize))> = found.into_iter().collect();
+        found.sort_by(|(a, rank_a), (b, rank_b)| {
+            let (a, b) = (&self.entries[*a as usize], &self.entries[*b as usize]);
+            rank_a
+                .cmp(rank_b)
+                .then(a.key.len().cmp(&b.key.len()))
+                .then(a.key.cmp(&b.key))
+        });
+        found
+            .into_iter()
+            .take(limit)
+            .map(|(i, (_, distance))| {
+                let entry = &self.entries[i as usize];
+                Suggested {
+                    text: entry.text.clone(),
+                    kind: entry.kind,
+                    distance,
+                    docs: entry.docs.clone(),
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,81 @@
+use std::ops::Range;
+
+use pulldown_cmark::{Event, Options, Parser};
+
+/// Extracts the plain text of markdown or html content, dropping the markup
+/// but keeping the text of code blocks and inline code
+pub fn plain_text(content: &str) -> String {
+    let mut text = String::with_capacity(content.len());
+    for event in Parser::new_ext(content, Options::all()) {
+        match event {
+            Event::Text(t) | Event::Code(t) => text.push_str(&t),
+            Event::Html(html) => text.push_str(&strip_tags(&html)),
+            Event::SoftBreak | Event::HardBreak | Event::End(_) => text.push(' '),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Removes the tags of an html fragment and decodes the common entities
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Normalises a tag or author name into the key it's indexed and linked by,
+/// lowercase with runs of whitespace and underscores replaced by a single `_` like resource slugs
+pub fn normalize(text: &str) -> String {
+    text.split(|c: char| c.is_whitespace() || c == '_')
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<&str>>()
+        .join("_")
+        .to_lowercase()
+}
+
+/// Splits text into lowercase terms, keeping `+` and `#` so `c++` and `c#` stay whole
+pub fn tokenize(text: &str) -> Vec<String> {
+    tokens(text).into_iter().map(|(_, term)| term).collect()
+}
+
+/// Like [`tokenize`], with the byte range of every term in the text
+pub fn tokens(text: &str) -> Vec<(Range<usize>, String)> {
+    let is_term_char = |c: char| c.is_alphanumeric() || c == '_' || c == '+' || c == '#';
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (start, is_term_char(c)) {
+            (None, true) => start = Some(i),
+            (Some(from), false) => {
+                let word = &text[from..i];
+                let trimmed = word.trim_start_matches(['+', '#']);
+                if !trimmed.is_empty() {
+                    let from = from + word.len() - trimmed.len();
+                    tokens.push((from..i, trimmed.to_lowercase()));
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
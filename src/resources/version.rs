@@ -0,0 +1,114 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::resources::cheatsheet::{Cheatsheet, Language};
+
+/// A dotted numeric version such as `3.8`, `1.70.0` or a Rust edition like `2021`
+///
+/// A leading `v` and anything after the numeric components (e.g. `3.12+`) is ignored,
+/// and missing components compare as zero so `3.8` equals `3.8.0`.
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
+pub struct Version {
+    parts: Vec<u32>,
+}
+
+impl Version {
+    pub fn new(parts: &[u32]) -> Self {
+        Self {
+            parts: parts.to_vec(),
+        }
+    }
+    fn part(&self, i: usize) -> u32 {
+        self.parts.get(i).copied().unwrap_or(0)
+    }
+}
+
+impl FromStr for Version {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().trim_start_matches(['v', 'V']);
+        let mut parts = Vec::new();
+        for part in s.split('.') {
+            let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+            match digits.parse() {
+                Ok(n) => parts.push(n),
+                Err(_) => break,
+            }
+            if digits.len() != part.len() {
+                break;
+            }
+        }
+        if parts.is_empty() {
+            return Err(());
+        }
+        Ok(Self { parts })
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self.parts.iter().map(|x| x.to_string()).collect();
+        write!(f, "{}", parts.join("."))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.parts.len().max(other.parts.len());
+        (0..len)
+            .map(|i| self.part(i).cmp(&other.part(i)))
+            .find(|x| x.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+/// The minimum language versions (and editions) content is expected to target,
+/// anything targeting an older version is considered stale
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VersionPolicy {
+    versions: HashMap<Language, Version>,
+    editions: HashMap<Language, Version>,
+}
+
+impl VersionPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Sets the minimum version of a language
+    pub fn min_version(mut self, lang: Language, version: Version) -> Self {
+        self.versions.insert(lang, version);
+        self
+    }
+    /// Sets the minimum edition of a language, e.g. `2021` for Rust
+    pub fn min_edition(mut self, lang: Language, edition: Version) -> Self {
+        self.editions.insert(lang, edition);
+        self
+    }
+    /// Checks if the cheat sheet targets a version or edition older than the minimum of its language.
+    /// Cheat sheets that don't state a version are never stale.
+    pub fn is_stale(&self, cheatsheet: &Cheatsheet) -> bool {
+        let lang = cheatsheet.language();
+        let older = |min: Option<&Version>, version: Option<Version>| match (min, version) {
+            (Some(min), Some(version)) => version < *min,
+            _ => false,
+        };
+        older(self.versions.get(&lang), cheatsheet.language_version())
+            || older(self.editions.get(&lang), cheatsheet.edition())
+    }
+}
@@ -0,0 +1,454 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag};
+
+use crate::process::{self, Exit, Limits};
+use crate::resources::cheatsheet::Language;
+use crate::resources::Cacher;
+
+/// A fenced code block extracted from a markdown resource
+///
+/// The fence's info string gives the language followed by optional attributes,
+/// for example ` ```rust,ignore ` or ` ```python no_run `.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    /// Where the snippet was found, a file path or a resource link
+    pub source: String,
+    /// The line of the opening fence (1-indexed)
+    pub line: usize,
+    pub lang: Language,
+    pub code: String,
+    pub attributes: Vec<String>,
+}
+
+impl Snippet {
+    /// The snippet won't be checked at all
+    pub fn is_ignored(&self) -> bool {
+        self.attributes.iter().any(|x| x == "ignore")
+    }
+    /// The snippet is only compile checked, even when the checker executes snippets
+    pub fn is_no_run(&self) -> bool {
+        self.attributes.iter().any(|x| x == "no_run")
+    }
+}
+
+/// Gets the language of a fence tag, accepting the common short names
+fn fence_language(tag: &str) -> Language {
+    match tag.to_lowercase().as_str() {
+        "rs" => Language::Rust,
+        "py" | "python3" => Language::Python,
+        "golang" => Language::Go,
+        "cpp" | "cxx" => Language::CPP,
+        "kt" => Language::Kotlin,
+        other => Language::from_str(other),
+    }
+}
+
+/// Extracts the fenced code blocks of a markdown document,
+/// code blocks without a known language are skipped
+pub fn extract(source: &str, markdown: &str) -> Vec<Snippet> {
+    let mut snippets = Vec::new();
+    let mut current: Option<Snippet> = None;
+    for (event, range) in Parser::new_ext(markdown, Options::all()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => {
+                let mut parts = info
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|x| !x.is_empty());
+                let lang = fence_language(parts.next().unwrap_or(""));
+                if lang == Language::Other {
+                    continue;
+                }
+                current = Some(Snippet {
+                    source: source.to_string(),
+                    line: markdown[..range.start].matches('\n').count() + 1,
+                    lang,
+                    code: String::new(),
+                    attributes: parts.map(|x| x.to_string()).collect(),
+                });
+            }
+            Event::Text(text) => {
+                if let Some(snippet) = current.as_mut() {
+                    snippet.code.push_str(&text);
+                }
+            }
+            Event::End(Tag::CodeBlock(_)) => snippets.extend(current.take()),
+            _ => {}
+        }
+    }
+    snippets
+}
+
+/// How to check the snippets of a language with a locally installed toolchain
+///
+/// In `args` and the `run` commands, `{file}` is replaced by the path of the snippet file and `{dir}`
+/// by its temporary directory. The snippet is wrapped in `harness` by replacing `{code}`, unless it
+/// already contains `skip_harness_if`, and `{imports}` by the `imports` lines of the packages the
+/// snippet uses.
+#[derive(Debug, Clone)]
+pub struct Toolchain {
+    pub program: String,
+    pub args: Vec<String>,
+    pub extension: String,
+    pub harness: String,
+    pub skip_harness_if: Option<String>,
+    /// The package names looked for as `name.` in a snippet, with the line importing them
+    pub imports: Vec<(String, String)>,
+    /// The commands building and executing a checked snippet, run in order
+    pub run: Vec<(String, Vec<String>)>,
+}
+
+impl Toolchain {
+    pub fn new(program: &str, args: &[&str], extension: &str) -> Self {
+        Self {
+            program: program.to_string(),
+            args: args.iter().map(|x| x.to_string()).collect(),
+            extension: extension.to_string(),
+            harness: "{code}".to_string(),
+            skip_harness_if: None,
+            imports: Vec::new(),
+            run: Vec::new(),
+        }
+    }
+    /// Wraps snippets in a harness unless they contain the `marker`
+    pub fn with_harness(mut self, harness: &str, marker: &str) -> Self {
+        self.harness = harness.to_string();
+        self.skip_harness_if = Some(marker.to_string());
+        self
+    }
+    /// Adds the import lines of packages, added to the harness when a snippet uses `name.`
+    pub fn with_imports(mut self, imports: &[(&str, &str)]) -> Self {
+        self.imports.extend(
+            imports
+                .iter()
+                .map(|(name, line)| (name.to_string(), line.to_string())),
+        );
+        self
+    }
+    /// Adds a command executing the snippet after the previous ones
+    pub fn with_run(mut self, program: &str, args: &[&str]) -> Self {
+        self.run.push((
+            program.to_string(),
+            args.iter().map(|x| x.to_string()).collect(),
+        ));
+        self
+    }
+    fn wrap(&self, code: &str) -> String {
+        match &self.skip_harness_if {
+            Some(marker) if code.contains(marker.as_str()) => code.to_string(),
+            _ => {
+                let imports: String = self
+                    .imports
+                    .iter()
+                    .filter(|(name, _)| uses_package(code, name))
+                    .map(|(_, line)| format!("{line}\n"))
+                    .collect();
+                self.harness
+                    .replace("{imports}", &imports)
+                    .replace("{code}", code)
+            }
+        }
+    }
+}
+
+/// Whether the code refers to `name.` outside of a longer identifier
+fn uses_package(code: &str, name: &str) -> bool {
+    let pattern = format!("{name}.");
+    code.match_indices(&pattern).any(|(i, _)| {
+        !code[..i]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.')
+    })
+}
+
+/// The standard library packages imported by the Go harness when a snippet uses them
+const GO_IMPORTS: [(&str, &str); 18] = [
+    ("bufio", "import \"bufio\""),
+    ("bytes", "import \"bytes\""),
+    ("context", "import \"context\""),
+    ("errors", "import \"errors\""),
+    ("filepath", "import \"path/filepath\""),
+    ("fmt", "import \"fmt\""),
+    ("http", "import \"net/http\""),
+    ("io", "import \"io\""),
+    ("json", "import \"encoding/json\""),
+    ("math", "import \"math\""),
+    ("os", "import \"os\""),
+    ("rand", "import \"math/rand\""),
+    ("sort", "import \"sort\""),
+    ("strconv", "import \"strconv\""),
+    ("strings", "import \"strings\""),
+    ("sync", "import \"sync\""),
+    ("time", "import \"time\""),
+    ("unicode", "import \"unicode\""),
+];
+
+/// The result of checking a single snippet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    /// The toolchain rejected the snippet, or executing it failed, with the given output
+    Failed(String),
+    /// Checking or executing the snippet ran past the time limit and it was killed
+    TimedOut,
+    /// The snippet is marked with `ignore`
+    Ignored,
+    /// No toolchain is configured or installed for the snippet's language
+    Unsupported,
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub snippet: Snippet,
+    pub outcome: Outcome,
+}
+
+impl CheckResult {
+    pub fn is_failure(&self) -> bool {
+        matches!(self.outcome, Outcome::Failed(_) | Outcome::TimedOut)
+    }
+}
+
+impl Display for CheckResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let snippet = &self.snippet;
+        match &self.outcome {
+            Outcome::Passed => write!(
+                f,
+                "{}:{}: {} ok",
+                snippet.source, snippet.line, snippet.lang
+            ),
+            Outcome::Failed(output) => write!(
+                f,
+                "{}:{}: {} snippet failed\n{}",
+                snippet.source, snippet.line, snippet.lang, output
+            ),
+            Outcome::TimedOut => write!(
+                f,
+                "{}:{}: {} snippet timed out",
+                snippet.source, snippet.line, snippet.lang
+            ),
+            Outcome::Ignored => write!(f, "{}:{}: ignored", snippet.source, snippet.line),
+            Outcome::Unsupported => write!(
+                f,
+                "{}:{}: no toolchain for {}",
+                snippet.source, snippet.line, snippet.lang
+            ),
+        }
+    }
+}
+
+/// Used to give every check its own temporary directory
+static CHECK_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// # Snippet Checker
+///
+/// Compile checks the code examples of resources using the locally installed toolchains,
+/// by default:
+/// - Rust: `rustc` (wrapped in `fn main` unless one is present)
+/// - Python: `python -m py_compile`
+/// - Go: `go vet` (wrapped in `package main` with the standard library imports it uses,
+///   unless a package is declared)
+/// - Zig: `zig ast-check`
+///
+/// With [`Checker::with_execution`], snippets that pass are also executed with the toolchain's
+/// `run` commands, by default `rustc` then the binary, `python` and `go run`, and fail if they
+/// exit unsuccessfully or run past the time limit. Snippets marked `no_run` are never executed.
+///
+/// Every toolchain command runs with the limits of the [`Grader`](crate::grader::Grader): stdin
+/// closed, a cleared environment and, on Unix, its own process group killed once it runs past
+/// the time limit.
+#[derive(Debug, Clone)]
+pub struct Checker {
+    toolchains: HashMap<Language, Toolchain>,
+    temp_root: PathBuf,
+    execute: bool,
+    time_limit: Duration,
+}
+
+impl Default for Checker {
+    fn default() -> Self {
+        let mut toolchains = HashMap::new();
+        toolchains.insert(
+            Language::Rust,
+            Toolchain::new(
+                "rustc",
+                &[
+                    "--edition",
+                    "2021",
+                    "--crate-type",
+                    "bin",
+                    "--emit=metadata",
+                    "--out-dir",
+                    "{dir}",
+                    "{file}",
+                ],
+                "rs",
+            )
+            .with_harness("fn main() {\n{code}\n}\n", "fn main")
+            .with_run(
+                "rustc",
+                &["--edition", "2021", "-o", "{dir}/snippet", "{file}"],
+            )
+            .with_run("{dir}/snippet", &[]),
+        );
+        toolchains.insert(
+            Language::Python,
+            Toolchain::new("python", &["-m", "py_compile", "{file}"], "py")
+                .with_run("python", &["{file}"]),
+        );
+        toolchains.insert(
+            Language::Go,
+            Toolchain::new("go", &["vet", "{file}"], "go")
+                .with_harness(
+                    "package main\n\n{imports}\nfunc main() {\n{code}\n}\n",
+                    "package ",
+                )
+                .with_imports(&GO_IMPORTS)
+                .with_run("go", &["run", "{file}"]),
+        );
+        toolchains.insert(
+            Language::Zig,
+            Toolchain::new("zig", &["ast-check", "{file}"], "zig"),
+        );
+        Self {
+            toolchains,
+            temp_root: std::env::temp_dir(),
+            execute: false,
+            time_limit: Duration::from_secs(10),
+        }
+    }
+}
+
+impl Checker {
+    /// Sets the toolchain used for a language
+    pub fn with_toolchain(mut self, lang: Language, toolchain: Toolchain) -> Self {
+        self.toolchains.insert(lang, toolchain);
+        self
+    }
+    /// Sets the directory the temporary snippet directories are created in
+    pub fn with_temp_root(mut self, temp_root: PathBuf) -> Self {
+        self.temp_root = temp_root;
+        self
+    }
+    /// Sets whether snippets not marked `no_run` are executed after they're compile checked
+    pub fn with_execution(mut self, execute: bool) -> Self {
+        self.execute = execute;
+        self
+    }
+    /// Sets the time each compile check and executed command may run before it's killed
+    pub fn with_time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = time_limit;
+        self
+    }
+
+    /// Checks a single snippet
+    pub fn check(&self, snippet: &Snippet) -> crate::Result<Outcome> {
+        if snippet.is_ignored() {
+            return Ok(Outcome::Ignored);
+        }
+        let toolchain = match self.toolchains.get(&snippet.lang) {
+            Some(toolchain) => toolchain,
+            None => return Ok(Outcome::Unsupported),
+        };
+
+        let dir = self.temp_root.join(format!(
+            "mokareads-snippet-{}-{}",
+            std::process::id(),
+            CHECK_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&dir)?;
+        let file = dir.join(format!("snippet.{}", toolchain.extension));
+        let outcome = std::fs::write(&file, toolchain.wrap(&snippet.code))
+            .map_err(|e| e.into())
+            .and_then(|_| self.run(toolchain, &dir, &file))
+            .and_then(|outcome| match outcome {
+                Outcome::Passed if self.execute && !snippet.is_no_run() => {
+                    self.execute(toolchain, &dir, &file)
+                }
+                outcome => Ok(outcome),
+            });
+        // a leftover directory in the temp root isn't worth losing the result over
+        let _ = std::fs::remove_dir_all(&dir);
+        outcome
+    }
+
+    /// Compile checks the snippet, killing the toolchain once it runs past the time limit
+    fn run(&self, toolchain: &Toolchain, dir: &Path, file: &Path) -> crate::Result<Outcome> {
+        let dir_str = dir.to_string_lossy();
+        let file_str = file.to_string_lossy();
+        let args: Vec<String> = toolchain
+            .args
+            .iter()
+            .map(|x| x.replace("{dir}", &dir_str).replace("{file}", &file_str))
+            .collect();
+        let limits = Limits::new(self.time_limit);
+        match process::run(&toolchain.program, &args, dir, "check", &limits) {
+            Ok(Exit::Exited { success: true, .. }) => Ok(Outcome::Passed),
+            Ok(Exit::Exited { stdout, stderr, .. }) => Ok(Outcome::Failed(
+                format!("{stderr}{stdout}").trim().to_string(),
+            )),
+            Ok(Exit::TimedOut) => Ok(Outcome::TimedOut),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Outcome::Unsupported),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Runs the toolchain's `run` commands, killing each once it runs past the time limit
+    fn execute(&self, toolchain: &Toolchain, dir: &Path, file: &Path) -> crate::Result<Outcome> {
+        let dir_str = dir.to_string_lossy();
+        let file_str = file.to_string_lossy();
+        let fill = |x: &str| x.replace("{dir}", &dir_str).replace("{file}", &file_str);
+        let limits = Limits::new(self.time_limit);
+        for (i, (program, args)) in toolchain.run.iter().enumerate() {
+            let args: Vec<String> = args.iter().map(|x| fill(x)).collect();
+            match process::run(&fill(program), &args, dir, &format!("run-{i}"), &limits)? {
+                Exit::Exited { success: true, .. } => {}
+                Exit::Exited { stdout, stderr, .. } => {
+                    return Ok(Outcome::Failed(
+                        format!("{stderr}{stdout}").trim().to_string(),
+                    ))
+                }
+                Exit::TimedOut => return Ok(Outcome::TimedOut),
+            }
+        }
+        Ok(Outcome::Passed)
+    }
+
+    /// Checks all the snippets, returning a result for each one
+    pub fn check_all(&self, snippets: &[Snippet]) -> crate::Result<Vec<CheckResult>> {
+        snippets
+            .iter()
+            .map(|snippet| {
+                Ok(CheckResult {
+                    snippet: snippet.clone(),
+                    outcome: self.check(snippet)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Checks the snippets of a markdown file, lines are reported relative to the file
+    pub fn check_file(&self, path: &Path) -> crate::Result<Vec<CheckResult>> {
+        let markdown = std::fs::read_to_string(path)?;
+        self.check_all(&extract(&path.to_string_lossy(), &markdown))
+    }
+
+    /// Checks the snippets of every resource in the cacher,
+    /// the source is the resource's link and lines are relative to its content.
+    ///
+    /// The resources must have been parsed raw, since parsed html no longer has fenced code blocks.
+    pub fn check_cacher(&self, cacher: &Cacher) -> crate::Result<Vec<CheckResult>> {
+        let snippets: Vec<Snippet> = cacher
+            .resources()
+            .flat_map(|x| extract(&x.link_short(), x.content()))
+            .collect();
+        self.check_all(&snippets)
+    }
+}
@@ -1,7 +1,11 @@
 #[cfg(test)]
 mod tests {
-    use mokareads_core::resources::article::{Article, Metadata};
-    use mokareads_core::resources::cheatsheet::Language;
+    use chrono::{TimeZone, Utc};
+    use mokareads_core::resources::article::{self, Article, Metadata, SeriesError};
+    use mokareads_core::resources::cheatsheet::{self, Cheatsheet, Language, Level};
+    use mokareads_core::resources::guide::Guide;
+    use mokareads_core::resources::locale::{self, Alternate};
+    use mokareads_core::resources::{Cacher, Parser, Resource, ResourceType, Searcher};
 
     // Define test data for an article
     fn create_test_article() -> Article {
@@ -36,6 +40,560 @@ mod tests {
         let rust_in_tags = article.lang_in_tag(Language::Rust);
 
         // Ensure that the 'rust_in_tags' variable is false since Rust is not in the tags
-        assert_eq!(rust_in_tags, false);
+        assert!(!rust_in_tags);
+    }
+
+    #[test]
+    fn test_article_languages() {
+        let article = Article::new(
+            Metadata::new(
+                "Futures",
+                "",
+                "Test Author",
+                "devicon",
+                "Rust, async, go-kit",
+            ),
+            String::new(),
+        );
+        // whole tags name languages, ignoring case
+        assert_eq!(article.languages(), vec![Language::Rust]);
+        // while `lang_in_tag` finds the name anywhere in the raw tags, case sensitively
+        assert!(!article.lang_in_tag(Language::Rust));
+        assert!(article.lang_in_tag(Language::C));
+        assert!(article.lang_in_tag(Language::Go));
+    }
+
+    #[test]
+    fn test_cacher_article_index() {
+        let mut cacher = Cacher::new(vec![create_test_article()], Vec::new(), Vec::new());
+        assert_eq!(
+            cacher.article("Test_Article").map(|x| x.title()),
+            Some("Test Article".to_string())
+        );
+        assert!(cacher.article("Missing").is_none());
+
+        // the index is rebuilt after deserializing
+        let json = serde_json::to_string(&cacher).unwrap();
+        let cacher_de: Cacher = serde_json::from_str(&json).unwrap();
+        assert!(cacher_de.article("Test_Article").is_some());
+
+        assert!(cacher.remove_article("Test_Article").is_some());
+        assert!(cacher.article("Test_Article").is_none());
+    }
+
+    #[test]
+    fn test_resources() {
+        let cheatsheet = Cheatsheet::new(
+            cheatsheet::Metadata::new("Traits", "Jane Doe", 2, "rust", "devicon"),
+            String::new(),
+        );
+        let cacher = Cacher::new(
+            vec![create_test_article()],
+            vec![cheatsheet],
+            vec![Guide::new("Contributing_Guide")],
+        );
+        let ids: Vec<String> = cacher.resources().map(|x| x.id()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "/articles/Test_Article",
+                "/cheatsheets/Traits#rust",
+                "/guides/Contributing_Guide"
+            ]
+        );
+
+        let cheatsheet = cacher.resource("/cheatsheets/Traits#rust").unwrap();
+        assert_eq!(cheatsheet.resource_type(), ResourceType::Cheatsheet);
+        assert_eq!(cheatsheet.languages(), vec![Language::Rust]);
+        assert_eq!(cheatsheet.difficulty(), Some(Level::Intermediate));
+        assert_eq!(cheatsheet.slug(), "Traits");
+
+        let guides: Vec<&dyn Resource> = cacher.resources_of(ResourceType::Guide).collect();
+        assert_eq!(guides.len(), 1);
+        assert_eq!(guides[0].title(), "Contributing Guide");
+        assert_eq!(
+            guides[0].link(),
+            "https://moka-reads.github.io/Contributing_Guide/"
+        );
+        assert!(guides[0].published().is_none());
+        assert_eq!(
+            cacher
+                .resources_of(ResourceType::Article)
+                .next()
+                .unwrap()
+                .tags(),
+            vec!["tag1", "tag2"]
+        );
+    }
+
+    fn create_series_article(title: &str, order: u32) -> Article {
+        let metadata = Metadata::new(title, "", "Test Author", "devicon", "rust")
+            .with_series("Async Rust", order);
+        Article::new(metadata, String::new())
+    }
+
+    #[test]
+    fn test_series() {
+        let markdown = "---
+title: Async Rust Part 2
+description: Futures
+author: Test Author
+icon: devicon
+date: 2023-01-01
+tags: rust
+series: Async Rust
+series_order: 2
+---
+Test content";
+        let article = Article::parse_raw(markdown);
+        assert_eq!(article.series(), Some("Async Rust"));
+        assert_eq!(article.series_order(), Some(2));
+        assert!(article.to_markdown().contains("series_order: 2"));
+        assert_eq!(create_test_article().series(), None);
+
+        let cacher = Cacher::new(
+            vec![
+                article,
+                create_series_article("Async Rust Part 3", 3),
+                create_series_article("Async Rust Part 1", 1),
+                create_test_article(),
+            ],
+            Vec::new(),
+            Vec::new(),
+        );
+        let series: Vec<&str> = cacher
+            .series("Async Rust")
+            .iter()
+            .map(|x| x.slug.as_str())
+            .collect();
+        assert_eq!(
+            series,
+            vec![
+                "Async_Rust_Part_1",
+                "Async_Rust_Part_2",
+                "Async_Rust_Part_3"
+            ]
+        );
+        let part_2 = cacher.article("Async_Rust_Part_2").unwrap();
+        assert_eq!(
+            cacher.previous_in_series(part_2).unwrap().slug,
+            "Async_Rust_Part_1"
+        );
+        assert_eq!(
+            cacher.next_in_series(part_2).unwrap().slug,
+            "Async_Rust_Part_3"
+        );
+        let part_1 = cacher.article("Async_Rust_Part_1").unwrap();
+        assert!(cacher.previous_in_series(part_1).is_none());
+        assert!(cacher.next_in_series(&create_test_article()).is_none());
+        assert!(cacher.validate_series().is_empty());
+
+        // the series of each locale are separate
+        let spanish = |order| {
+            let metadata = Metadata::new("Async Rust Part 1", "", "Test Author", "devicon", "")
+                .with_series("Async Rust", order)
+                .with_locale("es");
+            Article::new(metadata, String::new())
+        };
+        let mut cacher = cacher;
+        cacher.insert_article(spanish(3));
+        assert_eq!(cacher.series("Async Rust").len(), 3);
+        let series = cacher.series_in("Async Rust", "ES");
+        assert_eq!(series.len(), 1);
+        assert!(cacher.previous_in_series(series[0]).is_none());
+        assert_eq!(
+            cacher.validate_series(),
+            vec![SeriesError::Gap {
+                series: "Async Rust".to_string(),
+                locale: "es".to_string(),
+                from: 1,
+                to: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validate_series() {
+        let unordered = Article::parse_raw(
+            "---
+title: Async Rust Extra
+description: Pinning
+author: Test Author
+icon: devicon
+date: 2023-01-01
+tags: rust
+series: Async Rust
+---
+Test content",
+        );
+        let cacher = Cacher::new(
+            vec![
+                create_series_article("Async Rust Part 1", 1),
+                create_series_article("Async Rust Intro", 1),
+                unordered,
+                create_series_article("Async Rust Part 4", 4),
+            ],
+            Vec::new(),
+            Vec::new(),
+        );
+        let series = "Async Rust".to_string();
+        assert_eq!(
+            cacher.validate_series(),
+            vec![
+                SeriesError::MissingOrder {
+                    series: series.clone(),
+                    locale: "en".to_string(),
+                    article: "Async_Rust_Extra".to_string()
+                },
+                SeriesError::Duplicate {
+                    series: series.clone(),
+                    locale: "en".to_string(),
+                    order: 1,
+                    articles: vec![
+                        "Async_Rust_Part_1".to_string(),
+                        "Async_Rust_Intro".to_string()
+                    ]
+                },
+                SeriesError::Gap {
+                    series: series.clone(),
+                    locale: "en".to_string(),
+                    from: 2,
+                    to: 3
+                },
+            ]
+        );
+        assert_eq!(
+            SeriesError::Gap {
+                series: series.clone(),
+                locale: "en".to_string(),
+                from: 2,
+                to: 3
+            }
+            .to_string(),
+            "series `Async Rust` (en) is missing parts 2 to 3"
+        );
+
+        // a huge order is one gap, and order 0 is rejected
+        let sparse = Cacher::new(
+            vec![
+                create_series_article("Async Rust Zero", 0),
+                create_series_article("Async Rust Part 1", 1),
+                create_series_article("Async Rust Last", u32::MAX),
+            ],
+            Vec::new(),
+            Vec::new(),
+        );
+        assert_eq!(
+            sparse.validate_series(),
+            vec![
+                SeriesError::ZeroOrder {
+                    series: series.clone(),
+                    locale: "en".to_string(),
+                    article: "Async_Rust_Zero".to_string()
+                },
+                SeriesError::Gap {
+                    series,
+                    locale: "en".to_string(),
+                    from: 2,
+                    to: u32::MAX - 1
+                },
+            ]
+        );
+        // articles without an order come last
+        assert_eq!(
+            cacher.series("Async Rust").last().unwrap().slug,
+            "Async_Rust_Extra"
+        );
+    }
+
+    #[test]
+    fn test_drafts() {
+        let draft = Article::parse_raw(
+            "---
+title: Upcoming Release
+description: Test description
+author: Test Author
+icon: devicon
+date: 2023-01-01
+tags: rust
+draft: true
+---
+Test content",
+        );
+        assert!(draft.is_draft());
+        let scheduled = Article::new(
+            Metadata::new("Release Notes", "", "Test Author", "devicon", "rust")
+                .with_publish_at("2030-06-01"),
+            String::new(),
+        );
+        let cheatsheet = Cheatsheet::new(
+            cheatsheet::Metadata::new("Release Features", "Test Author", 1, "rust", "devicon")
+                .with_publish_at("2030-06-01T12:00:00+02:00"),
+            String::new(),
+        );
+        assert_eq!(
+            cheatsheet.publish_at(),
+            Some(Utc.with_ymd_and_hms(2030, 6, 1, 10, 0, 0).unwrap())
+        );
+        let cacher = Cacher::new(
+            vec![create_test_article(), draft, scheduled],
+            vec![cheatsheet],
+            Vec::new(),
+        );
+
+        let before = cacher.published_as_of(Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(before.articles().len(), 1);
+        assert!(before.cheatsheets().is_empty());
+        let after = cacher.published_as_of(Utc.with_ymd_and_hms(2030, 6, 1, 10, 0, 0).unwrap());
+        assert!(after.article("Release_Notes").is_some());
+        assert!(after.article("Upcoming_Release").is_none());
+        assert_eq!(after.cheatsheets().len(), 1);
+
+        // drafts and scheduled resources are left out of search and RSS unless previewing
+        let searcher = Searcher::new(&cacher);
+        assert!(searcher.search("release".to_string()).is_empty());
+        assert_eq!(cacher.articles_rss().items().len(), 1);
+        assert_eq!(article::articles_rss(cacher.articles()).items().len(), 1);
+        let preview = cacher.clone().with_preview(true);
+        let searcher = Searcher::new(&preview);
+        assert_eq!(searcher.search("release".to_string()).len(), 3);
+        assert_eq!(preview.articles_rss().items().len(), 3);
+
+        // inserting or updating applies the same check
+        let mut searcher = Searcher::new(&cacher);
+        assert_eq!(
+            searcher.expires_at(),
+            Some(Utc.with_ymd_and_hms(2030, 6, 1, 0, 0, 0).unwrap())
+        );
+        assert!(!searcher.refresh(&cacher));
+        assert!(!searcher.insert(cacher.article("Upcoming_Release").unwrap()));
+        assert!(!searcher.insert(cacher.article("Release_Notes").unwrap()));
+        assert!(searcher.search("release".to_string()).is_empty());
+        let test_article = create_test_article();
+        let unpublished = Article::new(
+            Metadata::new("Test Article", "", "Test Author", "devicon", "")
+                .with_publish_at("2031-01-01"),
+            String::new(),
+        );
+        assert!(searcher.update(&unpublished));
+        assert!(searcher.search("test".to_string()).is_empty());
+        assert!(!searcher.update(&test_article));
+        assert!(!searcher.insert(&test_article));
+        assert_eq!(searcher.search("test".to_string()).len(), 1);
+        let mut searcher = Searcher::new(&preview);
+        assert!(searcher.expires_at().is_none());
+        assert!(searcher.insert(cacher.article("Upcoming_Release").unwrap()));
+        assert_eq!(searcher.search("release".to_string()).len(), 3);
+
+        // a searcher is rebuilt once a resource left out of it is published
+        let soon = (Utc::now() + chrono::Duration::seconds(1)).to_rfc3339();
+        let cacher = Cacher::new(
+            vec![Article::new(
+                Metadata::new("Release Notes", "", "Test Author", "devicon", "rust")
+                    .with_publish_at(&soon),
+                String::new(),
+            )],
+            Vec::new(),
+            Vec::new(),
+        );
+        let mut searcher = Searcher::new(&cacher);
+        assert!(searcher.search("release".to_string()).is_empty());
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        assert!(searcher.refresh(&cacher));
+        assert_eq!(searcher.search("release".to_string()).len(), 1);
+        assert!(searcher.expires_at().is_none());
+
+        // an unreadable time keeps the article a draft
+        let unreadable = Article::new(
+            Metadata::new("Soon", "", "Test Author", "devicon", "").with_publish_at("next week"),
+            String::new(),
+        );
+        assert!(unreadable.is_draft());
+        assert!(!unreadable.is_published_at(Utc::now()));
+    }
+
+    fn create_translation(title: &str, locale: &str) -> Article {
+        Article::new(
+            Metadata::new(title, "", "Test Author", "devicon", "rust")
+                .with_locale(locale)
+                .with_translation_key("ownership"),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn test_locales() {
+        assert_eq!(locale::fallbacks("es_MX"), vec!["es-mx", "es", "en"]);
+        assert_eq!(locale::fallbacks("en-GB"), vec!["en-gb", "en"]);
+
+        let english = create_translation("Ownership", "");
+        let spanish = create_translation("Propiedad", "es");
+        let arabic = create_translation("Al Milkiya", "ar");
+        assert_eq!(english.locale(), "en");
+        assert_eq!(spanish.link_short(), "/es/articles/Propiedad");
+        let cacher = Cacher::new(
+            vec![english, spanish, arabic, create_test_article()],
+            Vec::new(),
+            Vec::new(),
+        );
+
+        assert_eq!(
+            cacher.article_in("Ownership", "es-MX").unwrap().slug,
+            "Propiedad"
+        );
+        assert_eq!(
+            cacher.article_in("Propiedad", "fr").unwrap().slug,
+            "Ownership"
+        );
+        assert_eq!(
+            cacher.article_in("Test_Article", "ar").unwrap().slug,
+            "Test_Article"
+        );
+        let arabic: Vec<&str> = cacher
+            .articles_in("ar")
+            .iter()
+            .map(|x| x.slug.as_str())
+            .collect();
+        assert_eq!(arabic, vec!["Al_Milkiya", "Test_Article"]);
+
+        let ownership = cacher.article("Ownership").unwrap();
+        assert_eq!(cacher.translations(ownership).len(), 3);
+        assert_eq!(
+            cacher.translation(ownership, "ar").unwrap().title(),
+            "Al Milkiya"
+        );
+        let alternates = cacher.alternates(ownership);
+        assert_eq!(
+            alternates
+                .iter()
+                .map(|x| x.locale.as_str())
+                .collect::<Vec<_>>(),
+            vec!["ar", "en", "es"]
+        );
+        assert_eq!(
+            alternates[2],
+            Alternate {
+                locale: "es".to_string(),
+                link: "https://moka-reads.mkproj.com/es/articles/Propiedad".to_string()
+            }
+        );
+        assert_eq!(
+            alternates[2].to_html(),
+            "<link rel=\"alternate\" hreflang=\"es\" href=\"https://moka-reads.mkproj.com/es/articles/Propiedad\">"
+        );
+
+        let rss = cacher.locale_rss("es");
+        assert_eq!(rss.language(), Some("es"));
+        assert_eq!(rss.items().len(), 1);
+        assert_eq!(rss.items()[0].title(), Some("Propiedad"));
+        let rss = cacher.articles_rss();
+        assert_eq!(rss.language(), Some("en"));
+        assert_eq!(rss.items().len(), 2);
+
+        let alternate = Alternate {
+            locale: "es\"".to_string(),
+            link: "https://moka-reads.mkproj.com/?a=1&b=<2>".to_string(),
+        };
+        assert_eq!(
+            alternate.to_html(),
+            "<link rel=\"alternate\" hreflang=\"es&quot;\" href=\"https://moka-reads.mkproj.com/?a=1&amp;b=&lt;2&gt;\">"
+        );
+    }
+
+    #[test]
+    fn test_same_slug_translations() {
+        let rust = |locale: &str, description: &str| {
+            Article::new(
+                Metadata::new("Rust", description, "Test Author", "devicon", "rust")
+                    .with_locale(locale),
+                String::new(),
+            )
+        };
+        let mut cacher = Cacher::new(
+            vec![rust("es", "Spanish"), rust("", "English")],
+            Vec::new(),
+            Vec::new(),
+        );
+        assert_eq!(cacher.article("Rust").unwrap().locale(), "en");
+        assert_eq!(
+            cacher
+                .localized_article("Rust", "ES")
+                .unwrap()
+                .description(),
+            "Spanish"
+        );
+        assert_eq!(cacher.article_in("Rust", "es").unwrap().locale(), "es");
+        assert_eq!(cacher.articles_in("es").len(), 1);
+        assert_eq!(cacher.alternates(cacher.article("Rust").unwrap()).len(), 2);
+
+        let replaced = cacher.insert_article(rust("es", "Nuevo")).unwrap();
+        assert_eq!(replaced.description(), "Spanish");
+        assert_eq!(cacher.articles().len(), 2);
+        assert_eq!(
+            cacher
+                .localized_article("Rust", "es")
+                .unwrap()
+                .description(),
+            "Nuevo"
+        );
+        assert_eq!(
+            cacher
+                .remove_localized_article("Rust", "es")
+                .unwrap()
+                .description(),
+            "Nuevo"
+        );
+        assert_eq!(cacher.article("Rust").unwrap().description(), "English");
+        assert!(cacher.localized_article("Rust", "es").is_none());
+    }
+
+    #[test]
+    fn test_translation_visibility() {
+        let draft = Article::new(
+            Metadata::new("Propiedad", "", "Test Author", "devicon", "rust")
+                .with_locale("es")
+                .with_translation_key("ownership")
+                .with_draft(true),
+            String::new(),
+        );
+        let cacher = Cacher::new(
+            vec![create_translation("Ownership", ""), draft],
+            Vec::new(),
+            Vec::new(),
+        );
+        let ownership = cacher.article("Ownership").unwrap();
+        assert_eq!(cacher.translations(ownership).len(), 1);
+        assert_eq!(
+            cacher.translation(ownership, "es").unwrap().title(),
+            "Ownership"
+        );
+        assert_eq!(
+            cacher.article_in("Ownership", "es").unwrap().slug,
+            "Ownership"
+        );
+        assert!(cacher.article_in("Propiedad", "es").is_some());
+        assert_eq!(cacher.articles_in("es")[0].slug, "Ownership");
+
+        let preview = cacher.with_preview(true);
+        let ownership = preview.article("Ownership").unwrap();
+        assert_eq!(preview.translations(ownership).len(), 2);
+        assert_eq!(
+            preview.article_in("Ownership", "es").unwrap().slug,
+            "Propiedad"
+        );
+        assert_eq!(preview.articles_in("es")[0].slug, "Propiedad");
+
+        // a draft without a visible translation isn't found in any locale
+        let cacher = Cacher::new(
+            vec![Article::new(
+                Metadata::new("Secret", "", "Test Author", "devicon", "").with_draft(true),
+                String::new(),
+            )],
+            Vec::new(),
+            Vec::new(),
+        );
+        assert!(cacher.article("Secret").is_some());
+        assert!(cacher.article_in("Secret", "en").is_none());
+        assert!(cacher.articles_in("en").is_empty());
     }
 }
@@ -0,0 +1,206 @@
+#[cfg(test)]
+mod tests {
+    use mokareads_core::resources::cheatsheet::{Cheatsheet, Language, Level, Metadata};
+    use mokareads_core::resources::learning_path::GraphError;
+    use mokareads_core::resources::version::{Version, VersionPolicy};
+    use mokareads_core::resources::{Cacher, Resource, Searcher};
+
+    fn cheatsheet(title: &str, level: u8, prerequisites: &[&str], next: &[&str]) -> Cheatsheet {
+        let metadata = Metadata::new(title, "Test Author", level, "rust", "devicon")
+            .with_prerequisites(prerequisites)
+            .with_next(next);
+        Cheatsheet::new(metadata, "Test content".to_string())
+    }
+
+    fn slugs(path: &[&Cheatsheet]) -> Vec<String> {
+        path.iter().map(|x| x.slug.clone()).collect()
+    }
+
+    #[test]
+    fn test_learning_path_order() {
+        let cacher = Cacher::new(
+            Vec::new(),
+            vec![
+                cheatsheet("Traits", 2, &["Structs"], &[]),
+                cheatsheet("Macros", 3, &[], &[]),
+                cheatsheet("Structs", 1, &["Basics"], &[]),
+                cheatsheet("Basics", 1, &[], &["Ownership"]),
+                // an advanced prerequisite pulls ahead of intermediate content
+                cheatsheet("Ownership", 2, &["Unsafe"], &[]),
+                cheatsheet("Unsafe", 3, &[], &[]),
+            ],
+            Vec::new(),
+        );
+
+        assert!(cacher.learning_graph().validate().is_empty());
+        let path = cacher.learning_path(Language::Rust).unwrap();
+        assert_eq!(
+            slugs(&path),
+            vec![
+                "Basics",
+                "Structs",
+                "Traits",
+                "Macros",
+                "Unsafe",
+                "Ownership"
+            ]
+        );
+        assert!(cacher.learning_path(Language::Go).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_learning_graph_errors() {
+        let cacher = Cacher::new(
+            Vec::new(),
+            vec![
+                cheatsheet("A", 1, &["C"], &[]),
+                cheatsheet("B", 1, &["A"], &["Missing"]),
+                cheatsheet("C", 1, &["B"], &[]),
+            ],
+            Vec::new(),
+        );
+
+        let errors = cacher.learning_graph().validate();
+        assert!(errors.contains(&GraphError::Dangling {
+            lang: Language::Rust,
+            from: "B".to_string(),
+            to: "Missing".to_string(),
+        }));
+        assert!(errors
+            .iter()
+            .any(|x| matches!(x, GraphError::Cycle { slugs, .. } if slugs.len() == 4)));
+        assert!(matches!(
+            cacher.learning_path(Language::Rust),
+            Err(GraphError::Cycle { .. })
+        ));
+    }
+
+    #[test]
+    fn test_learning_graph_languages() {
+        let other = |title: &str, lang: &str, prerequisites: &[&str]| {
+            let metadata = Metadata::new(title, "Test Author", 1, lang, "devicon")
+                .with_prerequisites(prerequisites);
+            Cheatsheet::new(metadata, "Test content".to_string())
+        };
+        let cacher = Cacher::new(
+            Vec::new(),
+            vec![
+                other("Monads", "haskell", &["Functors"]),
+                other("Functors", "ocaml", &[]),
+                other("Traits", "Rust", &["Basics"]),
+                other("Basics", "rust", &[]),
+            ],
+            Vec::new(),
+        );
+        // languages without a variant aren't linked to each other
+        let errors = cacher.learning_graph().validate();
+        assert_eq!(
+            errors,
+            vec![GraphError::Dangling {
+                lang: Language::Other,
+                from: "Monads".to_string(),
+                to: "Functors".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_cacher_cheatsheet_index() {
+        let mut cacher = Cacher::new(
+            Vec::new(),
+            vec![cheatsheet("Basics", 1, &[], &[])],
+            Vec::new(),
+        );
+        assert!(cacher.cheatsheet(Language::Rust, "Basics").is_some());
+        assert!(cacher.cheatsheet(Language::Python, "Basics").is_none());
+
+        let replaced = cacher.insert_cheatsheet(cheatsheet("Basics", 2, &[], &[]));
+        assert!(replaced.is_some());
+        assert_eq!(cacher.cheatsheets().len(), 1);
+        assert_eq!(
+            cacher
+                .cheatsheet(Language::Rust, "Basics")
+                .map(|x| x.level()),
+            Some(Level::Intermediate)
+        );
+
+        // languages without a variant don't collide as `Language::Other`
+        let unsupported = |lang: &str| {
+            Cheatsheet::new(
+                Metadata::new("Basics", "Test Author", 1, lang, "devicon"),
+                String::new(),
+            )
+        };
+        assert!(cacher.insert_cheatsheet(unsupported("haskell")).is_none());
+        assert!(cacher.insert_cheatsheet(unsupported("Elixir")).is_none());
+        assert_eq!(cacher.cheatsheets().len(), 3);
+        assert_eq!(
+            cacher.cheatsheet("elixir", "Basics").map(|x| x.lang()),
+            Some("Elixir".to_string())
+        );
+        assert!(cacher.remove_cheatsheet("haskell", "Basics").is_some());
+        assert!(cacher.cheatsheet("elixir", "Basics").is_some());
+        assert!(cacher.cheatsheet(Language::Other, "Basics").is_none());
+
+        // the language is read the same way it's looked up
+        let capitalized = unsupported(" Rust ");
+        assert_eq!(capitalized.language(), Language::Rust);
+        assert_eq!(capitalized.languages(), vec![Language::Rust]);
+        cacher.insert_cheatsheet(capitalized);
+        assert_eq!(
+            cacher
+                .cheatsheet(Language::Rust, "Basics")
+                .map(|x| x.lang()),
+            Some(" Rust ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_version_parsing() {
+        let v = |s: &str| s.parse::<Version>().unwrap();
+        assert!(v("3.8") < v("3.12"));
+        assert_eq!(v("3.8"), v("3.8.0"));
+        assert_eq!(v("v1.70+"), Version::new(&[1, 70]));
+        assert!("latest".parse::<Version>().is_err());
+    }
+
+    #[test]
+    fn test_version_filtering() {
+        let versioned = |title: &str, version: &str| {
+            let metadata = Metadata::new(title, "Test Author", 1, "python", "devicon")
+                .with_language_version(version);
+            Cheatsheet::new(metadata, "Test content".to_string())
+        };
+        let cacher = Cacher::new(
+            Vec::new(),
+            vec![
+                versioned("Walrus", "3.8"),
+                versioned("Match", "3.10"),
+                Cheatsheet::new(
+                    Metadata::new("Basics", "Test Author", 1, "python", "devicon"),
+                    "Test content".to_string(),
+                ),
+            ],
+            Vec::new(),
+        );
+        let min = "3.10".parse().unwrap();
+
+        let titles: Vec<String> = cacher
+            .cheatsheets_since(Language::Python, &min)
+            .iter()
+            .map(|x| x.title())
+            .collect();
+        assert_eq!(titles, vec!["Match", "Basics"]);
+        assert_eq!(
+            Searcher::new(&cacher)
+                .search_since("python".to_string(), &min)
+                .len(),
+            2
+        );
+
+        let policy = VersionPolicy::new().min_version(Language::Python, min);
+        let stale = cacher.stale_cheatsheets(&policy);
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].slug, "Walrus");
+    }
+}
@@ -0,0 +1,247 @@
+#[cfg(test)]
+mod tests {
+    use mokareads_core::resources::article::{self, Article};
+    use mokareads_core::resources::cheatsheet::{self, Cheatsheet, Language, Level};
+    use mokareads_core::resources::course::{self, Course, CourseError, Lesson};
+    use mokareads_core::resources::guide::Guide;
+    use mokareads_core::resources::{Cacher, Parser, Resource, ResourceType, Searcher};
+
+    fn create_test_cacher(lessons: &[&str]) -> Cacher {
+        let article = Article::new(
+            article::Metadata::new("Ownership Explained", "", "Jane Doe", "devicon", "rust"),
+            String::new(),
+        );
+        let cheatsheet = Cheatsheet::new(
+            cheatsheet::Metadata::new("Traits", "Jane Doe", 2, "rust", "devicon"),
+            String::new(),
+        );
+        let course = Course::new(
+            course::Metadata::new(
+                "Rust in 3 Lessons",
+                "From ownership to traits",
+                "Jane Doe",
+                1,
+                "devicon",
+            )
+            .with_lang("rust")
+            .with_lessons(lessons),
+            "Welcome to the course".to_string(),
+        );
+        Cacher::new(
+            vec![article],
+            vec![cheatsheet],
+            vec![Guide::new("Rust_Book")],
+        )
+        .with_courses(vec![course])
+    }
+
+    #[test]
+    fn test_parse_course() {
+        let markdown = "---
+title: Rust in 3 Lessons
+description: From ownership to traits
+author: Jane Doe
+level: 2
+lang: rust
+icon: devicon
+lessons:
+   - article/Ownership Explained
+   - cheatsheet/rust/Traits
+   - guide/Rust_Book
+---
+Welcome to the **course**";
+        let course = Course::parse(markdown);
+        assert_eq!(course.slug, "Rust_in_3_Lessons");
+        assert_eq!(course.level(), Level::Intermediate);
+        assert_eq!(course.languages(), vec![Language::Rust]);
+        assert!(course.content().contains("<strong>course</strong>"));
+        assert_eq!(
+            course.lessons(),
+            vec![
+                Lesson::Article {
+                    locale: "en".to_string(),
+                    slug: "Ownership_Explained".to_string()
+                },
+                Lesson::Cheatsheet {
+                    lang: "rust".to_string(),
+                    slug: "Traits".to_string()
+                },
+                Lesson::Guide("Rust_Book".to_string()),
+            ]
+        );
+        assert_eq!(course.lessons()[1].to_string(), "cheatsheet/rust/Traits");
+        assert!("video/Intro".parse::<Lesson>().is_err());
+        assert!("cheatsheet/Traits".parse::<Lesson>().is_err());
+        assert!("article//Traits".parse::<Lesson>().is_err());
+
+        // the lessons are kept when the course is deserialized
+        let json = serde_json::to_string(&course).unwrap();
+        let loaded: Course = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.lessons(), course.lessons());
+    }
+
+    #[test]
+    fn test_lesson_locale() {
+        let lesson: Lesson = "article/es_MX/Propiedad Explicada".parse().unwrap();
+        assert_eq!(
+            lesson,
+            Lesson::Article {
+                locale: "es-mx".to_string(),
+                slug: "Propiedad_Explicada".to_string()
+            }
+        );
+        assert_eq!(lesson.to_string(), "article/es-mx/Propiedad_Explicada");
+        assert_eq!(lesson.id(), "/es-mx/articles/Propiedad_Explicada");
+        let lesson: Lesson = "article/EN/Ownership_Explained".parse().unwrap();
+        assert_eq!(lesson.to_string(), "article/Ownership_Explained");
+
+        let translation = Article::new(
+            article::Metadata::new("Ownership Explained", "", "Jane Doe", "devicon", "rust")
+                .with_locale("es"),
+            String::new(),
+        );
+        let course = Course::new(
+            course::Metadata::new("Propiedad", "", "Jane Doe", 1, "devicon")
+                .with_lessons(&["article/es/Ownership_Explained", "cheatsheet/rust/Traits"]),
+            String::new(),
+        );
+        let cacher = Cacher::new(vec![translation], vec![], vec![]).with_courses(vec![course]);
+        let course = cacher.course("Propiedad").unwrap();
+        let translation = cacher
+            .localized_article("Ownership_Explained", "es")
+            .unwrap();
+        assert_eq!(course.position(translation), Some(0));
+        assert_eq!(
+            cacher.lesson(&course.lessons()[0]).unwrap().id(),
+            translation.id()
+        );
+        assert!(cacher.article("Ownership_Explained").is_some());
+        assert!(cacher
+            .lesson(&"article/Ownership_Explained".parse().unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn test_course_navigation() {
+        let cacher = create_test_cacher(&[
+            "article/Ownership_Explained",
+            "cheatsheet/rust/Traits",
+            "guide/Rust_Book",
+        ]);
+        assert!(cacher.validate_courses().is_empty());
+        let course = cacher.course("Rust_in_3_Lessons").unwrap();
+        let traits = cacher.cheatsheet(Language::Rust, "Traits").unwrap();
+
+        assert_eq!(course.position(traits), Some(1));
+        let previous = course.previous(traits).unwrap();
+        assert_eq!(
+            cacher.lesson(previous).unwrap().title(),
+            "Ownership Explained"
+        );
+        let next = course.next(traits).unwrap();
+        assert_eq!(cacher.lesson(next).unwrap().title(), "Rust Book");
+        assert!(course.next(&Guide::new("Rust_Book")).is_none());
+        assert!(course.previous(&Guide::new("Other")).is_none());
+        assert_eq!(cacher.courses_with(traits).len(), 1);
+
+        let searcher = Searcher::new(&cacher);
+        let results = searcher.search("course".to_string());
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].ty(), ResourceType::Course);
+        assert_eq!(results[0].link(), "/courses/Rust_in_3_Lessons");
+    }
+
+    #[test]
+    fn test_lesson_language_case() {
+        let lesson: Lesson = "cheatsheet/Rust/Traits".parse().unwrap();
+        assert_eq!(lesson.to_string(), "cheatsheet/rust/Traits");
+
+        // the language is matched the same way whatever its case in the lesson or the cheat sheet
+        let cheatsheet = Cheatsheet::new(
+            cheatsheet::Metadata::new("Traits", "Jane Doe", 2, "Rust", "devicon"),
+            String::new(),
+        );
+        let course = Course::new(
+            course::Metadata::new("Traits", "", "Jane Doe", 1, "devicon")
+                .with_lessons(&["cheatsheet/RUST/Traits"]),
+            String::new(),
+        );
+        let cacher = Cacher::new(vec![], vec![cheatsheet], vec![]).with_courses(vec![course]);
+        assert!(cacher.validate_courses().is_empty());
+        let traits = cacher.cheatsheet("rust", "Traits").unwrap();
+        assert_eq!(traits.id(), "/cheatsheets/Traits#rust");
+        let course = cacher.course("Traits").unwrap();
+        assert_eq!(course.position(traits), Some(0));
+    }
+
+    #[test]
+    fn test_validate_course() {
+        let cacher = create_test_cacher(&[
+            "article/Ownership_Explained",
+            "article/Lifetimes",
+            "cheatsheet/python/Traits",
+            "video/Intro",
+            "article/Ownership_Explained",
+        ]);
+        let errors = cacher.validate_courses();
+        let course = "Rust_in_3_Lessons".to_string();
+        assert_eq!(
+            errors,
+            vec![
+                CourseError::Unknown {
+                    course: course.clone(),
+                    lesson: "article/Lifetimes".to_string()
+                },
+                CourseError::Unknown {
+                    course: course.clone(),
+                    lesson: "cheatsheet/python/Traits".to_string()
+                },
+                CourseError::Invalid {
+                    course: course.clone(),
+                    lesson: "video/Intro".to_string()
+                },
+                CourseError::Duplicate {
+                    course,
+                    lesson: "article/Ownership_Explained".to_string()
+                },
+            ]
+        );
+
+        // lessons must be published, unless previewing
+        let draft = Article::new(
+            article::Metadata::new("Lifetimes", "", "Jane Doe", "devicon", "rust").with_draft(true),
+            String::new(),
+        );
+        let scheduled = Article::new(
+            article::Metadata::new("Async", "", "Jane Doe", "devicon", "rust")
+                .with_publish_at("2999-01-01T00:00:00Z"),
+            String::new(),
+        );
+        let mut cacher = create_test_cacher(&[
+            "article/Ownership_Explained",
+            "article/Lifetimes",
+            "article/Async",
+        ]);
+        cacher.insert_article(draft);
+        cacher.insert_article(scheduled);
+        let unpublished = |lesson: &str| CourseError::Unpublished {
+            course: "Rust_in_3_Lessons".to_string(),
+            lesson: lesson.to_string(),
+        };
+        assert_eq!(
+            cacher.validate_courses(),
+            vec![
+                unpublished("article/Lifetimes"),
+                unpublished("article/Async")
+            ]
+        );
+        assert!(cacher.with_preview(true).validate_courses().is_empty());
+
+        assert_eq!(
+            create_test_cacher(&[]).validate_courses(),
+            vec![CourseError::Empty {
+                course: "Rust_in_3_Lessons".to_string()
+            }]
+        );
+    }
+}
@@ -0,0 +1,229 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use mokareads_core::grader::{Grade, GradeError, Grader, Submission};
+    use mokareads_core::resources::cheatsheet::{Language, Level};
+    use mokareads_core::resources::exercise::{self, Exercise};
+    use mokareads_core::resources::{Cacher, Parser, Resource, ResourceType};
+
+    fn create_quiz() -> Exercise {
+        Exercise::new(
+            exercise::Metadata::new("Shared Ownership", "Jane Doe", 2, "rust", "devicon")
+                .with_choices(&["Box", "Rc", "Arc"], &[1, 2]),
+            "Which smart pointers share ownership of their value?".to_string(),
+        )
+    }
+
+    fn create_code_exercise(lang: &str, tests: &str) -> Exercise {
+        Exercise::new(
+            exercise::Metadata::new("Double It", "Jane Doe", 1, lang, "devicon")
+                .with_code("", tests),
+            "Write a function that doubles its argument.".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_parse_exercise() {
+        let markdown = "---
+title: Double It
+author: Jane Doe
+level: 1
+lang: rust
+icon: devicon
+starter: |
+  fn double(x: i32) -> i32 {
+      todo!()
+  }
+tests: |
+  fn main() {
+      assert_eq!(double(2), 4);
+  }
+---
+Write a function that **doubles** its argument.";
+        let exercise = Exercise::parse(markdown);
+        assert_eq!(exercise.slug, "Double_It");
+        assert!(!exercise.is_quiz());
+        assert_eq!(exercise.level(), Level::Beginner);
+        assert_eq!(exercise.languages(), vec![Language::Rust]);
+        assert!(exercise.starter().contains("todo!()"));
+        assert!(exercise.tests().contains("assert_eq!(double(2), 4);"));
+        assert!(exercise.content().contains("<strong>doubles</strong>"));
+
+        let hidden = exercise.without_solutions();
+        assert!(hidden.tests().is_empty());
+        assert!(!hidden.to_markdown().contains("tests"));
+        assert_eq!(hidden.starter(), exercise.starter());
+
+        let cacher =
+            Cacher::new(vec![], vec![], vec![]).with_exercises(vec![exercise, create_quiz()]);
+        assert_eq!(
+            cacher.exercise("Shared_Ownership").unwrap().choices(),
+            ["Box", "Rc", "Arc"]
+        );
+        assert_eq!(cacher.resources_of(ResourceType::Exercise).count(), 2);
+        assert!(cacher.resource("/exercises/Double_It").is_some());
+    }
+
+    #[test]
+    fn test_grade_quiz() {
+        let quiz = create_quiz();
+        let grader = Grader::default();
+        assert_eq!(
+            grader
+                .grade(&quiz, &Submission::Choices(vec![2, 1]))
+                .unwrap(),
+            Grade::Passed
+        );
+        assert!(matches!(
+            grader
+                .grade(&quiz, &Submission::Choices(vec![0, 1]))
+                .unwrap(),
+            Grade::Failed(_)
+        ));
+        assert!(quiz.without_solutions().answers().is_empty());
+
+        let error = grader
+            .grade(&quiz, &Submission::Choices(vec![3]))
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<GradeError>(),
+            Some(&GradeError::NoSuchChoice {
+                exercise: "Shared_Ownership".to_string(),
+                choice: 3
+            })
+        );
+        assert!(grader
+            .grade(&quiz, &Submission::Code(String::new()))
+            .is_err());
+    }
+
+    #[test]
+    fn test_grade_code() {
+        let exercise =
+            create_code_exercise("rust", "fn main() {\n    assert_eq!(double(2), 4);\n}\n");
+        let grader = Grader::default();
+        let right = Submission::Code("fn double(x: i32) -> i32 { x * 2 }".to_string());
+        let wrong = Submission::Code("fn double(x: i32) -> i32 { x + 1 }".to_string());
+        match grader.grade(&exercise, &right).unwrap() {
+            // rustc isn't installed
+            Grade::Unsupported => return,
+            grade => assert_eq!(grade, Grade::Passed),
+        }
+        assert!(matches!(
+            grader.grade(&exercise, &wrong).unwrap(),
+            Grade::Failed(_)
+        ));
+        assert!(grader
+            .grade(&exercise, &Submission::Choices(vec![0]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_grade_time_limit() {
+        let exercise = create_code_exercise("python", "");
+        let grader = Grader::default().with_time_limit(Duration::from_millis(500));
+        let grade = grader
+            .grade(
+                &exercise,
+                &Submission::Code("while True:\n    pass".to_string()),
+            )
+            .unwrap();
+        assert!(matches!(grade, Grade::TimedOut | Grade::Unsupported));
+    }
+
+    /// Grades the right and wrong submission, skipping when the toolchain isn't installed
+    fn assert_grades(lang: &str, tests: &str, right: &str, wrong: &[&str]) {
+        let exercise = create_code_exercise(lang, tests);
+        let grader = Grader::default();
+        match grader
+            .grade(&exercise, &Submission::Code(right.to_string()))
+            .unwrap()
+        {
+            Grade::Unsupported => return,
+            grade => assert_eq!(grade, Grade::Passed, "{lang}"),
+        }
+        for code in wrong {
+            let grade = grader
+                .grade(&exercise, &Submission::Code(code.to_string()))
+                .unwrap();
+            assert!(matches!(grade, Grade::Failed(_)), "{lang}: {code}");
+        }
+    }
+
+    #[test]
+    fn test_grade_rust() {
+        assert_grades(
+            "rust",
+            "fn main() {\n    assert_eq!(double(2), 4);\n}\n",
+            "fn double(x: i32) -> i32 { x * 2 }",
+            &[
+                "fn double(x: i32) -> i32 { std::process::exit(0) }",
+                "fn double(x: i32) -> i32 { println!(\"{sentinel}\"); std::process::exit(0) }",
+                "fn double(x: i32) -> i32 { x }\nfn main() {}",
+            ],
+        );
+        assert_grades(
+            "rust",
+            "fn main() -> Result<(), String> {\n    if double(2) == 4 { Ok(()) } else { Err(\"wrong\".into()) }\n}\n",
+            "fn double(x: i32) -> i32 { x * 2 }",
+            &["fn double(x: i32) -> i32 { x + 1 }"],
+        );
+    }
+
+    #[test]
+    fn test_grade_python() {
+        assert_grades(
+            "python",
+            "assert double(2) == 4\n",
+            "def double(x):\n    return x * 2\n",
+            &[
+                "def double(x):\n    return x + 1\n",
+                "import sys\nsys.exit(0)\n",
+                "import os\nos._exit(0)\n",
+            ],
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_grade_isolation() {
+        // a descriptor opened without close-on-exec, which the submission mustn't inherit
+        let fd = unsafe { libc::dup(2) };
+        assert!(fd > 2);
+        let tests = format!(
+            "import os, stat\n\
+             assert stat.S_IMODE(os.stat('.').st_mode) == 0o700\n\
+             try:\n    os.fstat({fd})\n    raise SystemExit('inherited')\n\
+             except OSError:\n    pass\n"
+        );
+        assert_grades("python", &tests, "", &["import os\nos.chmod('.', 0o755)\n"]);
+        unsafe { libc::close(fd) };
+    }
+
+    #[test]
+    fn test_grade_go() {
+        assert_grades(
+            "go",
+            "func main() {\n\tif double(2) != 4 {\n\t\tpanic(\"wrong\")\n\t}\n}\n",
+            "package main\n\nfunc double(x int) int { return x * 2 }\n",
+            &[
+                "package main\n\nfunc double(x int) int { return x + 1 }\n",
+                "package main\n\nimport \"os\"\n\nfunc double(x int) int { os.Exit(0); return 0 }\n",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_grade_zig() {
+        assert_grades(
+            "zig",
+            "pub fn main() !void {\n    if (double(2) != 4) return error.Wrong;\n}\n",
+            "fn double(x: i32) i32 {\n    return x * 2;\n}\n",
+            &[
+                "fn double(x: i32) i32 {\n    return x + 1;\n}\n",
+                "fn double(x: i32) i32 {\n    @import(\"std\").process.exit(0);\n}\n",
+            ],
+        );
+    }
+}
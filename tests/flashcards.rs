@@ -0,0 +1,141 @@
+#[cfg(test)]
+mod tests {
+    use mokareads_core::flashcards::{decks, from_cheatsheet, to_tsv};
+    use mokareads_core::resources::cheatsheet::{Cheatsheet, Language, Level, Metadata};
+
+    fn cheatsheet(title: &str, level: u8) -> Cheatsheet {
+        let content = "
+## Variables
+
+```rust
+let x = 5;
+```
+
+Make a binding mutable with `mut`:
+
+```rust
+let mut y = 6;
+```
+
+```text
+```
+";
+        Cheatsheet::new(
+            Metadata::new(title, "Test Author", level, "rust", "devicon"),
+            content.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_from_cheatsheet() {
+        let cards = from_cheatsheet(&cheatsheet("Basics", 1));
+        assert_eq!(cards.len(), 2);
+        assert_eq!(cards[0].front, "Variables");
+        assert_eq!(cards[0].back, "let x = 5;");
+        assert_eq!(cards[1].front, "Make a binding mutable with `mut`:");
+        assert_eq!(cards[1].source, "Basics");
+    }
+
+    #[test]
+    fn test_decks_tsv() {
+        let decks = decks(&[
+            cheatsheet("Generics", 2),
+            cheatsheet("Basics", 1),
+            cheatsheet("Loops", 1),
+        ]);
+        assert_eq!(decks.len(), 2);
+        assert_eq!(
+            (decks[0].lang, decks[0].level),
+            (Language::Rust, Level::Beginner)
+        );
+        assert_eq!(decks[0].cards.len(), 4);
+        assert_eq!(decks[1].name(), "MoKa Reads::Rust::Intermediate");
+
+        let tsv = to_tsv(&decks);
+        assert!(tsv.starts_with("#separator:tab\n"));
+        assert!(tsv.contains(
+            "Variables\t<pre><code>let x = 5;</code></pre>\tMoKa Reads::Rust::Beginner\tmokareads rust beginner Basics\n"
+        ));
+
+        let cpp = Cheatsheet::new(
+            Metadata::new("Basics", "Test Author", 1, "c++", "devicon"),
+            "## Output\n\n```cpp\nstd::cout << 1;\n```\n".to_string(),
+        );
+        assert_eq!(
+            mokareads_core::flashcards::decks(&[cpp])[0].name(),
+            "MoKa Reads::C++::Beginner"
+        );
+    }
+
+    #[cfg(feature = "anki")]
+    #[test]
+    fn test_write_apkg() {
+        use std::io::Read;
+
+        let compare = Cheatsheet::new(
+            Metadata::new("Comparisons", "Test Author", 1, "rust", "devicon"),
+            "Compare with `a < b`:\n\n```rust\nlet less = 1 < 2;\n```\n".to_string(),
+        );
+        let decks = decks(&[cheatsheet("Basics", 1), compare]);
+        let path = std::env::temp_dir().join("mokareads-test-deck.apkg");
+        mokareads_core::flashcards::write_apkg(&decks, &path).unwrap();
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+        let mut collection = Vec::new();
+        archive
+            .by_name("collection.anki2")
+            .unwrap()
+            .read_to_end(&mut collection)
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let db = std::env::temp_dir().join("mokareads-test-deck.anki2");
+        std::fs::write(&db, collection).unwrap();
+        let conn = rusqlite::Connection::open(&db).unwrap();
+
+        let decks_json: String = conn
+            .query_row("SELECT decks FROM col", [], |row| row.get(0))
+            .unwrap();
+        let cards: i64 = conn
+            .query_row("SELECT count(*) FROM cards", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(cards, 3);
+        let orphans: i64 = conn
+            .query_row(
+                "SELECT count(*) FROM cards WHERE nid NOT IN (SELECT id FROM notes)",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(orphans, 0);
+        let did: i64 = conn
+            .query_row("SELECT DISTINCT did FROM cards", [], |row| row.get(0))
+            .unwrap();
+        let decks_json: serde_json::Value = serde_json::from_str(&decks_json).unwrap();
+        assert_eq!(
+            decks_json[did.to_string()]["name"],
+            "MoKa Reads::Rust::Beginner"
+        );
+
+        let mut statement = conn
+            .prepare("SELECT flds, sfld, csum FROM notes ORDER BY id")
+            .unwrap();
+        let notes: Vec<(String, String, i64)> = statement
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .map(|x| x.unwrap())
+            .collect();
+        drop(statement);
+        drop(conn);
+        std::fs::remove_file(&db).unwrap();
+        assert_eq!(notes.len(), 3);
+        assert_eq!(
+            notes[0].0,
+            "Variables\x1f<pre><code>let x = 5;</code></pre>"
+        );
+        assert_eq!(notes[0].1, "Variables");
+        // the first 8 hex digits of the sha1 of "Variables"
+        assert_eq!(notes[0].2, 0xac01_8db1);
+        assert!(notes[2].0.starts_with("Compare with `a &lt; b`:\x1f"));
+        assert_eq!(notes[2].1, "Compare with `a < b`:");
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use mokareads_core::resources::article::{self, Article};
+    use mokareads_core::resources::cheatsheet::Language;
+    use mokareads_core::resources::glossary::{self, Glossary, Term};
+    use mokareads_core::resources::{Cacher, Parser, Resource, ResourceType};
+
+    fn create_test_glossary() -> Glossary {
+        Glossary::new(vec![
+            Term::new(
+                glossary::Metadata::new("Lifetime")
+                    .with_aliases(&["lifetimes"])
+                    .with_lang("rust"),
+                "<p>The region of code a reference is valid for.</p>".to_string(),
+            ),
+            Term::new(
+                glossary::Metadata::new("RAII"),
+                "<p>Resource Acquisition Is Initialization.</p>".to_string(),
+            ),
+            Term::new(
+                glossary::Metadata::new("Monad"),
+                "<p>A value in a context.</p>".to_string(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_parse_term() {
+        let markdown = "---
+term: Lifetime
+aliases:
+   - lifetimes
+lang: rust
+---
+The region of code a **reference** is valid for.";
+        let term = Term::parse(markdown);
+        assert_eq!(term.slug, "Lifetime");
+        assert_eq!(term.aliases(), ["lifetimes"]);
+        assert_eq!(term.languages(), vec![Language::Rust]);
+        assert_eq!(term.link_short(), "/glossary/Lifetime");
+        assert!(term.content().contains("<strong>reference</strong>"));
+        assert!(term.applies_to(&[Language::Rust]));
+        assert!(!term.applies_to(&[Language::Python]));
+
+        let cacher = Cacher::new(vec![], vec![], vec![]).with_glossary(vec![term]);
+        assert_eq!(cacher.term("Lifetime").unwrap().term(), "Lifetime");
+        assert_eq!(cacher.resources_of(ResourceType::Glossary).count(), 1);
+        assert_eq!(cacher.glossary().terms().len(), 1);
+    }
+
+    #[test]
+    fn test_link_terms() {
+        let glossary = create_test_glossary();
+        let html = "<h2>Lifetimes and RAII</h2>\
+<p>Every reference has a <em>lifetime</em>, and lifetimes are checked. RAII-style guards help, raii too.</p>\
+<pre><code>// a monad\n</code></pre>\
+<p>A <a href=\"/x\">monad</a> is not a monads, but a Monad is.</p>";
+        let linked = glossary.link_html(html, &[Language::Rust]);
+        assert_eq!(
+            linked,
+            "<h2>Lifetimes and RAII</h2>\
+<p>Every reference has a <em><a class=\"glossary-term\" href=\"/glossary/Lifetime\" title=\"Lifetime\">lifetime</a></em>, \
+and lifetimes are checked. <a class=\"glossary-term\" href=\"/glossary/RAII\" title=\"RAII\">RAII</a>-style guards help, raii too.</p>\
+<pre><code>// a monad\n</code></pre>\
+<p>A <a href=\"/x\">monad</a> is not a monads, but a \
+<a class=\"glossary-term\" href=\"/glossary/Monad\" title=\"Monad\">Monad</a> is.</p>"
+        );
+
+        // rust terms aren't linked from python resources
+        let python = glossary.link_html("<p>lifetime</p>", &[Language::Python]);
+        assert_eq!(python, "<p>lifetime</p>");
+
+        let article = Article::new(
+            article::Metadata::new("Ownership", "", "Jane Doe", "devicon", "rust"),
+            "<p>Borrows are limited by their <strong>lifetimes</strong>.</p>".to_string(),
+        );
+        assert!(glossary.link(&article).contains(
+            "<a class=\"glossary-term\" href=\"/glossary/Lifetime\" title=\"Lifetime\">lifetimes</a>"
+        ));
+        let untagged = Article::new(
+            article::Metadata::new("Ownership", "", "Jane Doe", "devicon", ""),
+            "lifetimes".to_string(),
+        );
+        assert_eq!(glossary.link(&untagged), "lifetimes");
+    }
+
+    #[test]
+    fn test_glossary_index() {
+        let index = create_test_glossary().index_html();
+        assert!(index.starts_with(
+            "<nav class=\"glossary-letters\"><a href=\"#glossary-l\">L</a><a href=\"#glossary-m\">M</a><a href=\"#glossary-r\">R</a></nav>"
+        ));
+        assert!(index.contains(
+            "<h2 id=\"glossary-r\">R</h2>\n<dl>\n<dt id=\"RAII\"><a href=\"/glossary/RAII\">RAII</a></dt>\n\
+<dd><p>Resource Acquisition Is Initialization.</p></dd>\n</dl>\n"
+        ));
+        let lifetime = index.find("id=\"Lifetime\"").unwrap();
+        let monad = index.find("id=\"Monad\"").unwrap();
+        assert!(lifetime < monad);
+    }
+}
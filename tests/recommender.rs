@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use mokareads_core::resources::article::{Article, Metadata};
+    use mokareads_core::resources::cheatsheet::{self, Cheatsheet};
+    use mokareads_core::resources::guide::Guide;
+    use mokareads_core::resources::recommender::Recommendation;
+    use mokareads_core::resources::Cacher;
+
+    fn article(title: &str, tags: &str, content: &str) -> Article {
+        let metadata = Metadata::new(title, "", "Jane Doe", "devicon", tags);
+        Article::new(metadata, content.to_string())
+    }
+
+    fn cheatsheet(title: &str, level: u8, content: &str) -> Cheatsheet {
+        let metadata = cheatsheet::Metadata::new(title, "John Doe", level, "rust", "devicon");
+        Cheatsheet::new(metadata, content.to_string())
+    }
+
+    #[test]
+    fn test_related() {
+        let cacher = Cacher::new(
+            vec![
+                article(
+                    "Ownership Explained",
+                    "rust, memory",
+                    "Every value has a single owner and is dropped when the owner goes out of scope.",
+                ),
+                article(
+                    "Borrowing Explained",
+                    "rust, memory",
+                    "A borrow lets code use a value without becoming its owner.",
+                ),
+                article(
+                    "Python Generators",
+                    "python",
+                    "Generators yield items lazily.",
+                ),
+            ],
+            vec![
+                cheatsheet("Traits", 2, "Traits describe shared behaviour."),
+                cheatsheet("Smart Pointers", 3, "Box, Rc and RefCell."),
+            ],
+            vec![Guide::new("Contributing")],
+        );
+        let recommender = cacher.recommender();
+        let titles = |related: Vec<Recommendation>| -> Vec<String> {
+            related
+                .iter()
+                .map(|x| x.metadata.title().to_string())
+                .collect()
+        };
+
+        let ownership = &cacher.articles()[0];
+        let related = recommender.related(ownership, 3);
+        assert_eq!(related.len(), 3);
+        assert_eq!(related[0].metadata.title(), "Borrowing Explained");
+        assert!(related.windows(2).all(|x| x[0].score >= x[1].score));
+        // nothing in common with python or the guide, and stop words like "and" don't count
+        assert_eq!(
+            titles(recommender.related(ownership, 10)),
+            vec!["Borrowing Explained", "Traits", "Smart Pointers"]
+        );
+
+        // closer levels rank higher between cheatsheets of a language
+        let traits = &cacher.cheatsheets()[0];
+        assert_eq!(
+            recommender.related(traits, 1)[0].metadata.title(),
+            "Smart Pointers"
+        );
+        assert!(recommender
+            .related(&Guide::new("Contributing"), 5)
+            .is_empty());
+        assert!(recommender
+            .related(&article("Unknown", "rust", ""), 5)
+            .is_empty());
+    }
+}
@@ -0,0 +1,797 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use mokareads_core::resources::article::{Article, Metadata};
+    use mokareads_core::resources::cheatsheet::{self, Cheatsheet};
+    use mokareads_core::resources::cheatsheet::{Language, Level};
+    use mokareads_core::resources::guide::Guide;
+    use mokareads_core::resources::searcher::analytics::{
+        JsonLinesSink, QueryKind, Report, RingBuffer,
+    };
+    use mokareads_core::resources::searcher::analyzer::Analyzer;
+    use mokareads_core::resources::searcher::bm25::TextIndex;
+    use mokareads_core::resources::searcher::export::{IndexError, FORMAT_VERSION};
+    use mokareads_core::resources::searcher::facets::FacetValue;
+    use mokareads_core::resources::searcher::page::{Cursor, SearchPage, SearchRequest, SortBy};
+    use mokareads_core::resources::searcher::persist::{PersistError, BINARY_VERSION};
+    use mokareads_core::resources::searcher::query::{Filter, Query};
+    use mokareads_core::resources::searcher::snippet::Snippet;
+    use mokareads_core::resources::searcher::suggest::{edit_distance, SuggestionKind};
+    use mokareads_core::resources::{Cacher, ResourceType, SearchMetadata, Searcher};
+
+    fn article(title: &str, description: &str, tags: &str, content: &str) -> Article {
+        let metadata = Metadata::new(title, description, "Jane Doe", "devicon", tags);
+        Article::new(metadata, content.to_string())
+    }
+
+    fn cheatsheet(title: &str, lang: &str, content: &str) -> Cheatsheet {
+        let metadata = cheatsheet::Metadata::new(title, "John Doe", 1, lang, "devicon");
+        Cheatsheet::new(metadata, content.to_string())
+    }
+
+    fn create_test_cacher() -> Cacher {
+        Cacher::new(
+            vec![
+                article(
+                    "Understanding the Borrow Checker",
+                    "How rust checks references",
+                    "rust, memory",
+                    "The **borrow checker** validates that references never outlive their data.",
+                ),
+                article(
+                    "Async Python",
+                    "Coroutines and event loops",
+                    "python, async",
+                    "Use `asyncio` to run coroutines, no borrow checker in sight.",
+                ),
+                article(
+                    "Memory in C",
+                    "Manual memory management",
+                    "c, memory",
+                    "<p>Call <code>free</code> on what you <em>malloc</em>.</p>",
+                ),
+            ],
+            vec![cheatsheet(
+                "Ownership",
+                "rust",
+                "# Borrowing\n\nA reference borrows a value.\n\n```rust\nlet r = &x;\n```",
+            )],
+            vec![Guide::new("Rust_Guide")],
+        )
+    }
+
+    #[test]
+    fn test_full_text_ranking() {
+        let searcher = Searcher::new(&create_test_cacher());
+        let hits = searcher.full_text("borrow checker");
+        let titles: Vec<&str> = hits.iter().map(|x| x.metadata.title()).collect();
+        // "borrows" and "borrowing" in the cheatsheet are stemmed to "borrow"
+        assert_eq!(
+            titles,
+            vec![
+                "Understanding the Borrow Checker",
+                "Async Python",
+                "Ownership"
+            ]
+        );
+        assert!(hits[0].score > hits[1].score);
+
+        // html content is indexed as plain text
+        let hits = searcher.full_text("malloc");
+        assert_eq!(hits[0].metadata.title(), "Memory in C");
+        assert!(searcher.full_text("nonexistent").is_empty());
+    }
+
+    #[test]
+    fn test_search_fallback() {
+        let searcher = Searcher::new(&create_test_cacher());
+        assert_eq!(searcher.search("Ownership".to_string()).len(), 1);
+        assert_eq!(
+            searcher.search("guide".to_string())[0].ty(),
+            ResourceType::Guide
+        );
+        // previously this would panic as it isn't a resource type
+        let results = searcher.search("coroutines".to_string());
+        assert_eq!(results[0].title(), "Async Python");
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("borrow", "borrow", 2), Some(0));
+        assert_eq!(edit_distance("borow", "borrow", 2), Some(1));
+        assert_eq!(edit_distance("brorow", "borrow", 2), Some(1));
+        assert_eq!(edit_distance("python", "borrow", 2), None);
+    }
+
+    #[test]
+    fn test_suggest() {
+        let searcher = Searcher::new(&create_test_cacher());
+        let texts = |input: &str| -> Vec<String> {
+            searcher
+                .suggest(input, 5)
+                .into_iter()
+                .map(|x| x.text)
+                .collect()
+        };
+
+        // prefix of a whole title or tag (shortest first), then of a word in a title
+        assert_eq!(texts("asy"), vec!["async", "Async Python"]);
+        assert_eq!(texts("the bor"), vec!["Understanding the Borrow Checker"]);
+        // typos in the last word
+        assert_eq!(texts("owenrship"), vec!["Ownership"]);
+        assert_eq!(texts("memroy"), vec!["memory", "Memory in C"]);
+        assert!(texts("zzz").is_empty());
+
+        let suggestion = &searcher.suggest("memory", 1)[0];
+        assert_eq!(suggestion.kind, SuggestionKind::Tag);
+        assert_eq!(suggestion.resources.len(), 2);
+    }
+
+    #[test]
+    fn test_query_parse() {
+        let query: Query = "lang:rust type:cheatsheet level:beginner ownership"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            query,
+            Query::And(vec![
+                Query::Filter(Filter::Lang(Language::Rust)),
+                Query::Filter(Filter::Type(ResourceType::Cheatsheet)),
+                Query::Filter(Filter::Level(Level::Beginner)),
+                Query::Term("ownership".to_string()),
+            ])
+        );
+
+        let query: Query = r#"author:"Jane" tag:async OR -"borrow checker""#.parse().unwrap();
+        assert_eq!(
+            query,
+            Query::Or(vec![
+                Query::And(vec![
+                    Query::Filter(Filter::Author("jane".to_string())),
+                    Query::Filter(Filter::Tag("async".to_string())),
+                ]),
+                Query::Not(Box::new(Query::Phrase("borrow checker".to_string()))),
+            ])
+        );
+
+        for (input, position) in [
+            ("lang:klingon", 0),
+            ("\"unterminated", 0),
+            ("(rust", 5),
+            ("rust OR", 7),
+            ("rust -", 6),
+            ("", 0),
+        ] {
+            let error = input.parse::<Query>().unwrap_err();
+            assert_eq!(error.position, position, "{input}: {error}");
+        }
+
+        // only known fields are filters, any other `word:` is full text
+        for (input, expected) in [
+            ("colour:red", Query::Term("colour:red".to_string())),
+            ("std::vec", Query::Term("std::vec".to_string())),
+            (
+                "Rust: ownership",
+                Query::And(vec![
+                    Query::Term("Rust:".to_string()),
+                    Query::Term("ownership".to_string()),
+                ]),
+            ),
+            (
+                "https://mokareads.org title:std::vec",
+                Query::And(vec![
+                    Query::Term("https://mokareads.org".to_string()),
+                    Query::Filter(Filter::Title("std::vec".to_string())),
+                ]),
+            ),
+            (
+                "author:\"Jane  Doe\"",
+                Query::Filter(Filter::Author("jane_doe".to_string())),
+            ),
+            // languages go through the synonyms, with the ones without a variant found by tag
+            ("lang:golang", Query::Filter(Filter::Lang(Language::Go))),
+            ("lang:RS", Query::Filter(Filter::Lang(Language::Rust))),
+            (
+                "lang:js",
+                Query::Filter(Filter::Tag("javascript".to_string())),
+            ),
+            (
+                "lang:javascript",
+                Query::Filter(Filter::Tag("javascript".to_string())),
+            ),
+        ] {
+            assert_eq!(input.parse::<Query>().unwrap(), expected, "{input}");
+        }
+
+        let nested = format!("{}rust{}", "(".repeat(100_000), ")".repeat(100_000));
+        let error = nested.parse::<Query>().unwrap_err();
+        assert_eq!(error.position, 64);
+        assert!("-".repeat(100_000).parse::<Query>().is_err());
+        let deep_enough = format!("{}rust{}", "(".repeat(32), ")".repeat(32));
+        assert_eq!(
+            deep_enough.parse::<Query>().unwrap(),
+            Query::Term("rust".to_string())
+        );
+    }
+
+    #[test]
+    fn test_query_search() {
+        let searcher = Searcher::new(&create_test_cacher());
+        let titles = |input: &str| -> Vec<String> {
+            searcher
+                .query(input)
+                .unwrap()
+                .into_iter()
+                .map(|x| x.metadata.title().to_string())
+                .collect()
+        };
+
+        assert_eq!(titles("lang:rust type:cheatsheet"), vec!["Ownership"]);
+        assert_eq!(titles("lang:rs type:cheatsheet"), vec!["Ownership"]);
+        assert_eq!(
+            titles("\"borrow checker\" -tag:python"),
+            vec!["Understanding the Borrow Checker"]
+        );
+        assert_eq!(titles("\"checker borrow\""), Vec::<String>::new());
+        assert_eq!(
+            titles("author:\"jane doe\" tag:memory (malloc OR references)"),
+            vec!["Memory in C", "Understanding the Borrow Checker"]
+        );
+        // authors are matched whole, like tags
+        assert_eq!(titles("author:jane"), Vec::<String>::new());
+        assert_eq!(titles("author:JANE_DOE tag:memory").len(), 2);
+        assert_eq!(
+            titles("title:borrow"),
+            vec!["Understanding the Borrow Checker"]
+        );
+        assert_eq!(
+            titles("level:1 OR type:guide"),
+            vec!["Ownership", "Rust Guide"]
+        );
+        assert!(searcher.query("type:podcast").is_err());
+    }
+
+    #[test]
+    fn test_incremental_updates() {
+        let mut searcher = Searcher::new(&create_test_cacher());
+        assert_eq!(searcher.len(), 5);
+
+        let generics = article(
+            "Rust Generics",
+            "Traits and monomorphization",
+            "rust, types",
+            "Generic functions are monomorphized.",
+        );
+        assert!(!searcher.insert(&generics));
+        assert_eq!(searcher.len(), 6);
+        assert_eq!(searcher.search("Rust Generics".to_string()).len(), 1);
+        assert_eq!(
+            searcher.full_text("monomorphized")[0].metadata.title(),
+            "Rust Generics"
+        );
+        let titles: Vec<String> = searcher
+            .search("rust".to_string())
+            .iter()
+            .map(|x| x.title().to_string())
+            .collect();
+        // cheatsheets come before the articles of a language
+        assert_eq!(
+            titles,
+            vec![
+                "Ownership",
+                "Understanding the Borrow Checker",
+                "Rust Generics"
+            ]
+        );
+        let synonym: Vec<String> = searcher
+            .search("RS".to_string())
+            .iter()
+            .map(|x| x.title().to_string())
+            .collect();
+        assert_eq!(synonym, titles);
+        assert_eq!(searcher.suggest("typ", 5)[0].text, "types");
+
+        // updating replaces the old version everywhere
+        let edited = article(
+            "Rust Generics",
+            "Traits and bounds",
+            "rust, traits",
+            "Generic functions are specialised.",
+        );
+        assert!(searcher.update(&edited));
+        assert_eq!(searcher.len(), 6);
+        assert!(searcher.full_text("monomorphized").is_empty());
+        assert_eq!(searcher.full_text("specialised").len(), 1);
+        assert!(searcher.suggest("typ", 5).is_empty());
+        assert_eq!(searcher.query("tag:traits").unwrap().len(), 1);
+
+        let unknown = article("Zig Comptime", "", "zig", "");
+        assert!(!searcher.update(&unknown));
+        assert!(searcher.remove(&unknown).is_none());
+
+        let removed = searcher.remove(&edited).unwrap();
+        assert_eq!(removed.title(), "Rust Generics");
+        assert_eq!(searcher.len(), 5);
+        assert!(searcher.full_text("specialised").is_empty());
+        assert!(searcher
+            .query("-tag:memory")
+            .unwrap()
+            .iter()
+            .all(|x| x.metadata.title() != "Rust Generics"));
+        assert_eq!(searcher.search("rust".to_string()).len(), 2);
+
+        // cheatsheets with the same link in different languages are separate resources
+        assert!(!searcher.insert(&cheatsheet("Ownership", "c", "Who frees the memory?")));
+        assert_eq!(searcher.search("Ownership".to_string()).len(), 2);
+    }
+
+    #[test]
+    fn test_compaction() {
+        let mut searcher = Searcher::new(&create_test_cacher());
+        let size = searcher.to_bytes().unwrap().len();
+
+        // the slots of replaced versions are reclaimed instead of piling up
+        for i in 0..200 {
+            let version = article(
+                "Rust Generics",
+                "Traits and bounds",
+                "rust, traits",
+                &format!("Generic functions, version {i}."),
+            );
+            searcher.insert(&version);
+        }
+        assert_eq!(searcher.len(), 6);
+        assert!(searcher.to_bytes().unwrap().len() < size * 2);
+        assert_eq!(searcher.full_text("199").len(), 1);
+        assert!(searcher.full_text("198").is_empty());
+
+        searcher.remove(&create_test_cacher().articles()[1]);
+        let borrow = searcher.full_text("borrow checker");
+        searcher.compact();
+        assert_eq!(searcher.len(), 5);
+        assert_eq!(
+            searcher.full_text("validates")[0].metadata.title(),
+            "Understanding the Borrow Checker"
+        );
+        assert!((searcher.full_text("borrow checker")[0].score - borrow[0].score).abs() < 1e-4);
+        let titles = |input: &str| -> Vec<String> {
+            searcher
+                .query(input)
+                .unwrap()
+                .into_iter()
+                .map(|x| x.metadata.title().to_string())
+                .collect()
+        };
+        assert_eq!(
+            titles("tag:rust -type:cheatsheet"),
+            vec!["Understanding the Borrow Checker", "Rust Generics"]
+        );
+        assert_eq!(
+            titles("tag:traits OR (tag:memory NOT malloc)"),
+            vec!["Understanding the Borrow Checker", "Rust Generics"]
+        );
+        assert_eq!(searcher.suggest("rust gen", 1)[0].resources.len(), 1);
+    }
+
+    #[test]
+    fn test_decode_hostile_postings() {
+        let postings = |bytes: &[u8]| vec![("rust".to_string(), bytes.to_vec())];
+
+        // one posting of document 0 with a term frequency of 1 at position 0
+        let index = TextIndex::decode(&postings(&[1, 0, 2, 1, 0]), 1).unwrap();
+        assert_eq!(index.docs_with("rust"), vec![0]);
+
+        // a count of u32::MAX postings with none following fails without allocating them
+        assert!(TextIndex::decode(&postings(&[0xff, 0xff, 0xff, 0xff, 0x0f]), 1).is_none());
+        // 2^32 doesn't fit a u32, instead of wrapping around to a count of 0
+        assert!(TextIndex::decode(&postings(&[0x80, 0x80, 0x80, 0x80, 0x10]), 1).is_none());
+        // a varint that never ends
+        assert!(TextIndex::decode(&postings(&[0xff; 6]), 1).is_none());
+        // a document past the number of documents
+        assert!(TextIndex::decode(&postings(&[1, 1, 2, 1, 0]), 1).is_none());
+    }
+
+    #[test]
+    fn test_snippets() {
+        let searcher = Searcher::new(&create_test_cacher());
+        let hits = searcher.full_text("references");
+        let snippet = hits[0].snippet.as_ref().unwrap();
+        assert_eq!(
+            snippet.text,
+            "The borrow checker validates that references never outlive their data."
+        );
+        assert_eq!(snippet.highlights, vec![34..44]);
+        assert_eq!(&snippet.text[34..44], "references");
+        assert!(snippet.html.contains("<mark>references</mark>"));
+
+        // the description is used when the content doesn't match
+        let snippet = &searcher.full_text("coroutines event")[0].snippet;
+        assert_eq!(
+            snippet.as_ref().unwrap().html,
+            "Use asyncio to run <mark>coroutines</mark>, no borrow checker in sight."
+        );
+        let hits = searcher.query("tag:c").unwrap();
+        assert_eq!(
+            hits[0].snippet.as_ref().unwrap().text,
+            "Manual memory management"
+        );
+
+        let long = format!("{} needle {}", "hay ".repeat(40), "<stack> ".repeat(40));
+        let analyzer = Analyzer::default();
+        let snippet = Snippet::around(&long, &analyzer.analyze("needles"), &analyzer).unwrap();
+        assert!(snippet.text.starts_with("… hay"));
+        assert!(snippet.text.ends_with(" …"));
+        assert!(snippet.html.contains("<mark>needle</mark> &lt;stack&gt;"));
+        assert!(Snippet::around(&long, &analyzer.analyze("missing"), &analyzer).is_none());
+    }
+
+    #[test]
+    fn test_facets() {
+        let searcher = Searcher::new(&create_test_cacher());
+        let counts = |values: &[FacetValue]| -> Vec<(String, usize)> {
+            values.iter().map(|x| (x.label.clone(), x.count)).collect()
+        };
+        let owned = |values: &[(&str, usize)]| -> Vec<(String, usize)> {
+            values.iter().map(|(x, n)| (x.to_string(), *n)).collect()
+        };
+
+        // an empty query counts every resource
+        let response = searcher.faceted("", &[]).unwrap();
+        assert_eq!(response.hits.len(), 5);
+        let facets = &response.facets;
+        assert_eq!(
+            counts(&facets.langs),
+            owned(&[("rust", 2), ("c", 1), ("python", 1)])
+        );
+        assert_eq!(
+            counts(&facets.types),
+            owned(&[("Article", 3), ("Cheatsheet", 1), ("Guide", 1)])
+        );
+        assert_eq!(counts(&facets.levels), owned(&[("Beginner", 1)]));
+        assert_eq!(
+            counts(&facets.tags),
+            owned(&[
+                ("memory", 2),
+                ("async", 1),
+                ("c", 1),
+                ("python", 1),
+                ("rust", 1)
+            ])
+        );
+        assert_eq!(
+            counts(&facets.authors),
+            owned(&[("Jane Doe", 3), ("John Doe", 1)])
+        );
+
+        // drilling down with a facet's filter narrows the results and the counts
+        let rust = facets.langs[0].filter.clone();
+        assert_eq!(rust, Filter::Lang(Language::Rust));
+        let response = searcher.faceted("borrow", &[rust]).unwrap();
+        let titles: Vec<&str> = response.hits.iter().map(|x| x.metadata.title()).collect();
+        assert_eq!(
+            titles,
+            vec!["Ownership", "Understanding the Borrow Checker"]
+        );
+        assert_eq!(
+            counts(&response.facets.types),
+            owned(&[("Article", 1), ("Cheatsheet", 1)])
+        );
+
+        let author = Filter::Author("john doe".to_string());
+        let response = searcher.faceted("", &[author]).unwrap();
+        assert_eq!(response.hits.len(), 1);
+        let jane = facets.authors[0].filter.clone();
+        assert_eq!(jane, Filter::Author("jane_doe".to_string()));
+        assert_eq!(searcher.faceted("", &[jane]).unwrap().hits.len(), 3);
+        assert!(searcher.faceted("(", &[]).is_err());
+    }
+
+    #[test]
+    fn test_search_pages() {
+        let cacher = create_test_cacher();
+        let dates = ["2023-05-01", "2021-01-15", "2024-11-30"];
+        let articles: Vec<Article> = cacher
+            .articles()
+            .iter()
+            .zip(dates)
+            .map(|(x, date)| {
+                let tags = x.tags().join(", ");
+                let metadata =
+                    Metadata::new(&x.title(), &x.description(), "Jane Doe", "devicon", &tags);
+                Article::new(metadata.with_date(date), x.content().to_string())
+            })
+            .collect();
+        let cacher = Cacher::new(articles, cacher.cheatsheets(), cacher.guides());
+        let searcher = Searcher::new(&cacher);
+        let titles = |page: &SearchPage| -> Vec<String> {
+            page.hits
+                .iter()
+                .map(|x| x.metadata.title().to_string())
+                .collect()
+        };
+
+        let request = SearchRequest::new("").with_sort(SortBy::Date).with_limit(2);
+        let page = searcher.execute(&request).unwrap();
+        assert_eq!(page.total, 5);
+        assert_eq!(
+            titles(&page),
+            vec!["Memory in C", "Understanding the Borrow Checker"]
+        );
+
+        // the cursor continues after the last hit even if a resource before it is removed
+        let cursor = page.next.clone().unwrap();
+        let mut updated = searcher.clone();
+        updated.remove(&cacher.articles()[2]);
+        let page = updated
+            .execute(&request.clone().with_cursor(cursor.clone()))
+            .unwrap();
+        assert_eq!(page.offset, 1);
+        assert_eq!(titles(&page), vec!["Async Python", "Ownership"]);
+
+        let page = searcher
+            .execute(&request.clone().with_cursor(cursor))
+            .unwrap();
+        assert_eq!(page.offset, 2);
+        let page = searcher.execute(&request.with_offset(4)).unwrap();
+        assert_eq!(page.hits.len(), 1);
+        assert!(page.next.is_none());
+
+        let request = SearchRequest::new("type:article").with_sort(SortBy::Title);
+        let page = searcher.execute(&request).unwrap();
+        assert_eq!(
+            titles(&page),
+            vec![
+                "Async Python",
+                "Memory in C",
+                "Understanding the Borrow Checker"
+            ]
+        );
+        assert_eq!(page.facets.types.len(), 1);
+
+        let request = SearchRequest::new("")
+            .with_sort(SortBy::Level)
+            .with_limit(1);
+        let page = searcher.execute(&request).unwrap();
+        assert_eq!(titles(&page), vec!["Ownership"]);
+        let cursor: Cursor = page.next.unwrap().to_string().parse().unwrap();
+        let page = searcher.execute(&request.with_cursor(cursor)).unwrap();
+        assert_eq!(page.offset, 1);
+
+        let request = SearchRequest::new("memory").with_filters(&[Filter::Tag("c".to_string())]);
+        let page = searcher.execute(&request).unwrap();
+        assert_eq!(titles(&page), vec!["Memory in C"]);
+        assert_eq!(page.total, 1);
+    }
+
+    #[test]
+    fn test_export_index() {
+        let mut searcher = Searcher::new(&create_test_cacher());
+        let json = searcher.to_json().unwrap();
+        assert!(json.len() < serde_json::to_string(&searcher).unwrap().len());
+
+        let loaded = Searcher::from_json(&json).unwrap();
+        assert_eq!(loaded.len(), searcher.len());
+        for query in ["borrow checker", "malloc", "memory rust"] {
+            let scores = |searcher: &Searcher| -> Vec<(String, f32)> {
+                searcher
+                    .full_text(query)
+                    .into_iter()
+                    .map(|x| (x.metadata.title().to_string(), x.score))
+                    .collect()
+            };
+            assert_eq!(scores(&loaded), scores(&searcher));
+        }
+        assert_eq!(
+            loaded.query("\"borrow checker\" lang:rust").unwrap().len(),
+            1
+        );
+        assert_eq!(loaded.search("rust".to_string()).len(), 2);
+        assert_eq!(loaded.suggest("memroy", 5).len(), 2);
+
+        // the text is only exported on request, for snippets
+        let text = serde_json::to_string(&searcher.export_with_text()).unwrap();
+        assert!(json.len() < text.len());
+        assert!(!json.contains("\"content\""));
+        assert!(loaded.full_text("malloc")[0].snippet.is_none());
+        let full = Searcher::import(searcher.export_with_text()).unwrap();
+        assert_eq!(
+            full.full_text("malloc")[0].snippet,
+            searcher.full_text("malloc")[0].snippet
+        );
+
+        // removed resources are left out and the others renumbered
+        let removed = &create_test_cacher().articles()[0];
+        searcher.remove(removed);
+        let loaded = Searcher::from_json(&searcher.to_json().unwrap()).unwrap();
+        assert_eq!(loaded.len(), 4);
+        assert_eq!(
+            loaded.full_text("malloc")[0].metadata.title(),
+            "Memory in C"
+        );
+        assert_eq!(loaded.full_text("borrow checker").len(), 2);
+
+        let version = format!("\"version\":{FORMAT_VERSION}");
+        let future = json.replacen(&version, "\"version\":99", 1);
+        assert_eq!(
+            Searcher::from_json(&future).unwrap_err(),
+            IndexError::UnsupportedVersion(99)
+        );
+        assert!(Searcher::from_json("{}").is_err());
+    }
+
+    #[test]
+    fn test_tags_and_authors() {
+        let mut searcher = Searcher::new(&create_test_cacher());
+        let titles = |metas: Vec<SearchMetadata>| -> Vec<String> {
+            metas.iter().map(|x| x.title().to_string()).collect()
+        };
+
+        assert_eq!(
+            titles(searcher.tag(" Memory ")),
+            vec!["Understanding the Borrow Checker", "Memory in C"]
+        );
+        assert_eq!(searcher.tags()[0], ("memory".to_string(), 2));
+
+        let profile = searcher.author("jane_doe").unwrap();
+        assert_eq!(profile.name, "Jane Doe");
+        assert_eq!(profile.articles.len(), 3);
+        assert!(profile.cheatsheets.is_empty());
+        assert_eq!(profile.tags[0], ("memory".to_string(), 2));
+        assert_eq!(
+            profile.langs,
+            vec![Language::Rust, Language::C, Language::Python]
+        );
+        assert!(searcher.author("nobody").is_none());
+
+        // tags are normalised on both sides
+        let article = article(
+            "Deep Learning in Rust",
+            "Tensors",
+            "Machine  Learning, rust",
+            "",
+        );
+        searcher.insert(&article);
+        assert_eq!(
+            titles(searcher.tag("machine_learning")),
+            vec!["Deep Learning in Rust"]
+        );
+        assert_eq!(searcher.query("tag:\"machine learning\"").unwrap().len(), 1);
+        assert_eq!(
+            searcher.authors(),
+            vec![("jane_doe".to_string(), 4), ("john_doe".to_string(), 1)]
+        );
+
+        searcher.remove(&article);
+        assert!(searcher.tag("machine learning").is_empty());
+        assert_eq!(searcher.author("JANE DOE").unwrap().articles.len(), 3);
+        let profile = searcher.author("John Doe").unwrap();
+        assert_eq!(titles(profile.cheatsheets), vec!["Ownership"]);
+    }
+
+    #[test]
+    fn test_analyzer() {
+        let analyzer = Analyzer::default();
+        assert_eq!(analyzer.analyze("Closures"), analyzer.analyze("closure"));
+        assert_eq!(analyzer.analyze("golang"), vec!["go"]);
+        assert_eq!(analyzer.analyze("CPP and js"), vec!["c++", "javascript"]);
+        assert_eq!(analyzer.analyze("The Café"), vec!["cafe"]);
+        assert_eq!(analyzer.analyze("utf8 c#"), vec!["utf8", "c#"]);
+        assert_eq!(
+            Analyzer::plain().analyze("The Closures"),
+            vec!["the", "closures"]
+        );
+        let custom = Analyzer::plain().with_synonyms(&[("k8s", "kubernetes")]);
+        assert_eq!(custom.analyze("K8s"), vec!["kubernetes"]);
+
+        let mut searcher = Searcher::new(&create_test_cacher());
+        searcher.insert(&article(
+            "Closures in Go",
+            "Functions capturing variables",
+            "go",
+            "A closure captures the variables of its scope.",
+        ));
+        for query in ["closures", "Closure", "golang closure"] {
+            assert_eq!(
+                searcher.full_text(query)[0].metadata.title(),
+                "Closures in Go"
+            );
+        }
+        // stop words are ignored in queries instead of matching nothing
+        assert_eq!(searcher.query("the closures").unwrap().len(), 1);
+        let snippet = searcher.full_text("capturing")[0].snippet.clone().unwrap();
+        assert_eq!(
+            snippet.html,
+            "A closure <mark>captures</mark> the variables of its scope."
+        );
+
+        let plain = Searcher::with_analyzer(&create_test_cacher(), Analyzer::plain());
+        assert_eq!(plain.full_text("borrow checker").len(), 2);
+    }
+
+    #[test]
+    fn test_analytics() {
+        let buffer = Arc::new(RingBuffer::new(4));
+        let path = std::env::temp_dir().join(format!("searches-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let file = Arc::new(JsonLinesSink::open(&path).unwrap());
+
+        let searcher = Searcher::new(&create_test_cacher()).with_observer(buffer.clone());
+        searcher.search("rust".to_string());
+        searcher.full_text("Haskell");
+        searcher.full_text("haskell ");
+        assert!(searcher.query("(").is_err());
+        searcher
+            .execute(&SearchRequest::new("memory").with_limit(1))
+            .unwrap();
+        searcher.suggest("mem", 5);
+
+        // the query error and the suggestion aren't recorded, and the oldest event was dropped
+        let events = buffer.events();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0].kind, QueryKind::Search);
+        assert_eq!(events[0].results, 2);
+        assert_eq!(events[3].kind, QueryKind::Request);
+        assert_eq!(events[3].results, 2);
+
+        let report = buffer.report(5);
+        assert_eq!(report.queries, 4);
+        assert_eq!(report.zero_results, 2);
+        assert_eq!(report.top_queries[0], ("haskell".to_string(), 2));
+        assert_eq!(report.top_zero_results, vec![("haskell".to_string(), 2)]);
+
+        let searcher = searcher.with_observer(file);
+        searcher.full_text("zig comptime");
+        searcher.full_text("borrow");
+        let events = JsonLinesSink::read(&path).unwrap();
+        let report = Report::new(&events, 1);
+        assert_eq!(report.queries, 2);
+        assert_eq!(
+            report.top_zero_results,
+            vec![("zig comptime".to_string(), 1)]
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_persist() {
+        let mut searcher = Searcher::new(&create_test_cacher());
+        searcher.remove(&create_test_cacher().articles()[1]);
+        let path = std::env::temp_dir().join(format!("searcher-{}.bin", std::process::id()));
+        searcher.save(&path).unwrap();
+
+        let loaded = Searcher::load(&path).unwrap();
+        assert_eq!(loaded.len(), 4);
+        assert_eq!(
+            loaded.full_text("borrow checker")[0].score,
+            searcher.full_text("borrow checker")[0].score
+        );
+        assert_eq!(loaded.suggest("memroy", 5).len(), 2);
+        assert_eq!(loaded.author("jane doe").unwrap().articles.len(), 2);
+        #[cfg(feature = "mmap")]
+        assert_eq!(Searcher::load_mmap(&path).unwrap().len(), 4);
+        std::fs::remove_file(&path).unwrap();
+
+        let bytes = searcher.to_bytes().unwrap();
+        let mut corrupt = bytes.clone();
+        *corrupt.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            Searcher::from_bytes(&corrupt),
+            Err(PersistError::Checksum { .. })
+        ));
+        assert!(matches!(
+            Searcher::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(PersistError::Truncated)
+        ));
+        let mut future = bytes.clone();
+        future[8..12].copy_from_slice(&(BINARY_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            Searcher::from_bytes(&future),
+            Err(PersistError::UnsupportedVersion(_))
+        ));
+        assert!(matches!(
+            Searcher::from_bytes(b"{}"),
+            Err(PersistError::NotAnIndex)
+        ));
+    }
+}
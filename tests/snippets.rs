@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use mokareads_core::resources::cheatsheet::Language;
+    use mokareads_core::snippets::{extract, Checker, Outcome, Toolchain};
+
+    const MARKDOWN: &str = "# Ownership
+
+```rust
+let s = String::from(\"hello\");
+```
+
+```rust,ignore
+this isn't rust
+```
+
+```python no_run
+print('hi')
+```
+
+```text
+not code
+```
+";
+
+    #[test]
+    fn test_extract() {
+        let snippets = extract("ownership.md", MARKDOWN);
+        assert_eq!(snippets.len(), 3);
+        assert_eq!(snippets[0].lang, Language::Rust);
+        assert_eq!(snippets[0].line, 3);
+        assert_eq!(snippets[0].code, "let s = String::from(\"hello\");\n");
+        assert!(snippets[1].is_ignored());
+        assert_eq!(snippets[2].lang, Language::Python);
+        assert!(snippets[2].is_no_run());
+    }
+
+    #[test]
+    fn test_check_rust() {
+        let checker = Checker::default();
+        let snippets = extract("ownership.md", MARKDOWN);
+        assert_eq!(checker.check(&snippets[0]).unwrap(), Outcome::Passed);
+        assert_eq!(checker.check(&snippets[1]).unwrap(), Outcome::Ignored);
+
+        let broken = extract("broken.md", "```rust\nlet x: u8 = \"nope\";\n```\n");
+        let results = checker.check_all(&broken).unwrap();
+        assert!(results[0].is_failure());
+        assert!(results[0]
+            .to_string()
+            .starts_with("broken.md:1: rust snippet failed"));
+    }
+
+    #[test]
+    fn test_execution() {
+        let markdown =
+            "```rust\npanic!(\"boom\");\n```\n\n```rust,no_run\npanic!(\"boom\");\n```\n";
+        let snippets = extract("panics.md", markdown);
+        assert_eq!(
+            Checker::default().check(&snippets[0]).unwrap(),
+            Outcome::Passed
+        );
+
+        let checker = Checker::default().with_execution(true);
+        let outcome = checker.check(&snippets[0]).unwrap();
+        assert!(matches!(&outcome, Outcome::Failed(output) if output.contains("boom")));
+        assert_eq!(checker.check(&snippets[1]).unwrap(), Outcome::Passed);
+    }
+
+    #[test]
+    fn test_harness_imports() {
+        let checker = Checker::default().with_execution(true).with_toolchain(
+            Language::Python,
+            Toolchain::new("python", &["-m", "py_compile", "{file}"], "py")
+                .with_harness("{imports}{code}", "import ")
+                .with_imports(&[("math", "import math"), ("json", "import missing_module")])
+                .with_run("python", &["{file}"]),
+        );
+        let snippets = extract(
+            "math.md",
+            "```python\nmyjson = 1\nprint(math.pi, myjson.real)\n```\n",
+        );
+        match checker.check(&snippets[0]).unwrap() {
+            // python isn't installed
+            Outcome::Unsupported => {}
+            outcome => assert_eq!(outcome, Outcome::Passed),
+        }
+    }
+
+    #[test]
+    fn test_missing_toolchain() {
+        let checker = Checker::default().with_toolchain(
+            Language::Python,
+            Toolchain::new("mokareads-missing-python", &["{file}"], "py"),
+        );
+        let snippets = extract("ownership.md", MARKDOWN);
+        assert_eq!(checker.check(&snippets[2]).unwrap(), Outcome::Unsupported);
+    }
+
+    #[test]
+    fn test_check_time_limit() {
+        let checker = Checker::default()
+            .with_time_limit(Duration::from_millis(300))
+            .with_toolchain(
+                Language::Python,
+                Toolchain::new("python", &["-c", "while True: pass", "{file}"], "py"),
+            );
+        let snippets = extract("ownership.md", MARKDOWN);
+        let outcome = checker.check(&snippets[2]).unwrap();
+        // python isn't installed
+        assert!(matches!(outcome, Outcome::TimedOut | Outcome::Unsupported));
+    }
+}